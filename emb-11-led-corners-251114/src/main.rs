@@ -1,54 +1,87 @@
 #![no_std]
 #![no_main]
 
+use core::cell::RefCell;
+use cortex_m::interrupt::Mutex;
 use cortex_m_rt::entry;
-use embedded_hal::{digital::OutputPin, delay::DelayNs};
-use nrf52833_hal::{ Timer, gpio, pac, timer};
+use embedded_hal::delay::DelayNs;
+use led_matrix::{
+    animation::Image,
+    driver::{Config, MatrixDriver},
+    sprite,
+};
+use nrf52833_hal::{
+    Timer, gpio,
+    gpio::{Level, Output, Pin, PushPull},
+    pac::{self, TIMER1, interrupt},
+    timer::Periodic,
+};
 use panic_halt as _;
 
-struct LedPins {
-    row1: gpio::p0::P0_21<gpio::Output<gpio::PushPull>>,
-    row2: gpio::p0::P0_22<gpio::Output<gpio::PushPull>>,
-    row3: gpio::p0::P0_15<gpio::Output<gpio::PushPull>>,
-    row4: gpio::p0::P0_24<gpio::Output<gpio::PushPull>>,
-    row5: gpio::p0::P0_19<gpio::Output<gpio::PushPull>>,
-    col1: gpio::p0::P0_28<gpio::Output<gpio::PushPull>>,
-    col2: gpio::p0::P0_11<gpio::Output<gpio::PushPull>>,
-    col3: gpio::p0::P0_31<gpio::Output<gpio::PushPull>>,
-    col4: gpio::p1::P1_05<gpio::Output<gpio::PushPull>>,
-    col5: gpio::p0::P0_30<gpio::Output<gpio::PushPull>>,
+// The rows and columns are scanned by the matrix driver from the TIMER1 interrupt now,
+// main only picks what to show and no longer has to keep the pins going itself
+
+type LedPin = Pin<Output<PushPull>>;
+
+struct LedMatrix {
+    driver: MatrixDriver<LedPin, LedPin>,
+    timer: Timer<TIMER1, Periodic>,
 }
 
-impl LedPins {
-    fn init(p0: gpio::p0::Parts, p1: gpio::p1::Parts) -> Self {
-        Self {
-            row1: p0.p0_21.into_push_pull_output(gpio::Level::Low),
-            row2: p0.p0_22.into_push_pull_output(gpio::Level::Low),
-            row3: p0.p0_15.into_push_pull_output(gpio::Level::Low),
-            row4: p0.p0_24.into_push_pull_output(gpio::Level::Low),
-            row5: p0.p0_19.into_push_pull_output(gpio::Level::Low),
-
-            col1: p0.p0_28.into_push_pull_output(gpio::Level::High),
-            col2: p0.p0_11.into_push_pull_output(gpio::Level::High),
-            col3: p0.p0_31.into_push_pull_output(gpio::Level::High),
-            col4: p1.p1_05.into_push_pull_output(gpio::Level::High),
-            col5: p0.p0_30.into_push_pull_output(gpio::Level::High),
+static SHARED_MATRIX: Mutex<RefCell<Option<LedMatrix>>> = Mutex::new(RefCell::new(None));
+
+// Clockwise round the corners, the corner just left stays dimly lit
+const CORNERS: [Image; 4] = [
+    sprite!("#....\n.....\n.....\n.....\n3...."),
+    sprite!("3...#\n.....\n.....\n.....\n....."),
+    sprite!("....3\n.....\n.....\n.....\n....#"),
+    sprite!(".....\n.....\n.....\n.....\n#...3"),
+];
+
+fn init_matrix(p0: gpio::p0::Parts, p1: gpio::p1::Parts, timer: pac::TIMER1) {
+    let rows = [
+        p0.p0_21.into_push_pull_output(Level::Low).degrade(),
+        p0.p0_22.into_push_pull_output(Level::Low).degrade(),
+        p0.p0_15.into_push_pull_output(Level::Low).degrade(),
+        p0.p0_24.into_push_pull_output(Level::Low).degrade(),
+        p0.p0_19.into_push_pull_output(Level::Low).degrade(),
+    ];
+    let cols = [
+        p0.p0_28.into_push_pull_output(Level::High).degrade(),
+        p0.p0_11.into_push_pull_output(Level::High).degrade(),
+        p0.p0_31.into_push_pull_output(Level::High).degrade(),
+        p1.p1_05.into_push_pull_output(Level::High).degrade(),
+        p0.p0_30.into_push_pull_output(Level::High).degrade(),
+    ];
+    let config = Config::MICROBIT;
+    let driver = MatrixDriver::new(rows, cols, config).unwrap();
+
+    let mut timer = Timer::periodic(timer);
+    timer.enable_interrupt();
+    timer.start(config.tick_us());
+
+    cortex_m::interrupt::free(|cs| {
+        SHARED_MATRIX.borrow(cs).replace(Some(LedMatrix { driver, timer }));
+    });
+    unsafe { pac::NVIC::unmask(pac::interrupt::TIMER1) };
+}
+
+fn show(image: Image) {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(matrix) = SHARED_MATRIX.borrow(cs).borrow_mut().as_mut() {
+            matrix.driver.show_image(image);
         }
-    }
+    })
+}
 
-    fn reset(&mut self) {
-        self.row1.set_low().unwrap();
-        self.row2.set_low().unwrap();
-        self.row3.set_low().unwrap();
-        self.row4.set_low().unwrap();
-        self.row5.set_low().unwrap();
-
-        self.col1.set_high().unwrap();
-        self.col2.set_high().unwrap();
-        self.col3.set_high().unwrap();
-        self.col4.set_high().unwrap();
-        self.col5.set_high().unwrap();
-    }
+#[interrupt]
+fn TIMER1() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(matrix) = SHARED_MATRIX.borrow(cs).borrow_mut().as_mut() {
+            matrix.timer.reset_event();
+            matrix.driver.tick().unwrap();
+        }
+    })
 }
 
 #[entry]
@@ -57,50 +90,17 @@ fn main() -> ! {
 
     let p0 = gpio::p0::Parts::new(peripherals.P0);
     let p1 = gpio::p1::Parts::new(peripherals.P1);
-    let mut timer0 = timer::Timer::new(peripherals.TIMER0);
+    let mut timer0 = Timer::new(peripherals.TIMER0);
 
-    let mut led_pins = LedPins::init(p0, p1);
+    init_matrix(p0, p1, peripherals.TIMER1);
 
-    let delay = 2;
+    let delay = 250;
     let mut index = 0;
 
-    loop{
-        match index {
-            0 => left_top(&mut led_pins),
-            1 => right_top(&mut led_pins),
-            2 => right_bottom(&mut led_pins),
-            3 => left_bottom(&mut led_pins),
-            _ => (),
-        }
-        reset_pins_with_delay(&mut led_pins, &mut timer0, delay);
+    loop {
+        show(CORNERS[index]);
+        timer0.delay_ms(delay);
 
-        index += 1;
-        if index > 3 { index = 0 };
+        index = (index + 1) % CORNERS.len();
     }
 }
-
-fn reset_pins_with_delay(led_pins: &mut LedPins, timer0: &mut Timer<pac::TIMER0>, delay: u32) {
-    timer0.delay_ms(delay);
-    led_pins.reset();
-    timer0.delay_ms(delay);
-}
-
-fn left_top(led_pins: &mut LedPins) {
-    led_pins.row1.set_high().unwrap();
-    led_pins.col1.set_low().unwrap();
-}
-
-fn right_top(led_pins: &mut LedPins) {
-    led_pins.row1.set_high().unwrap();
-    led_pins.col5.set_low().unwrap();
-}
-
-fn left_bottom(led_pins: &mut LedPins) {
-    led_pins.row5.set_high().unwrap();
-    led_pins.col1.set_low().unwrap();
-}
-
-fn right_bottom(led_pins: &mut LedPins) {
-    led_pins.row5.set_high().unwrap();
-    led_pins.col5.set_low().unwrap();
-}
\ No newline at end of file
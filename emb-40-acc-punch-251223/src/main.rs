@@ -4,9 +4,11 @@
 use cortex_m_rt::entry;
 use microbit::{
     board, 
+    display::blocking::Display,
     hal::{Timer, twim}, 
     pac::twim0::{frequency::FREQUENCY_A},
 };
+use led_matrix::widgets::{PeakMeter, Range};
 use lsm303agr::{AccelMode, AccelOutputDataRate, Lsm303agr};
 use rtt_target::{rtt_init_print, rprintln};
 use panic_rtt_target as _;
@@ -14,12 +16,20 @@ use panic_rtt_target as _;
 const THRESHOLD: f32 = 1.5;
 const DURATION: i32 = 100;
 
+// The whole 8 g scale as a bar, the peak stays up for about half a second before it sinks
+const METER_RANGE: Range = Range::new(0, 8000);
+const PEAK_HOLD: u32 = 50;
+const PEAK_DECAY: u32 = 1;
+const FRAME_MS: u32 = 10;
+
 #[entry]
 fn main() -> ! {
     rtt_init_print!();
     let board = board::Board::take().unwrap();
     let i2c = twim::Twim::new(board.TWIM0, board.i2c_internal.into(), FREQUENCY_A::K100);
     let mut timer = Timer::new(board.TIMER0);
+    let mut display = Display::new(board.display_pins);
+    let mut meter = PeakMeter::new(METER_RANGE, PEAK_HOLD, PEAK_DECAY);
 
     let mut sensor = Lsm303agr::new_with_i2c(i2c);
     sensor.init().unwrap();
@@ -34,7 +44,10 @@ fn main() -> ! {
 
     loop {
         if sensor.accel_status().unwrap().xyz_new_data() {
-            let abs_x = sensor.acceleration().unwrap().x_mg().abs() as f32 / 1000.0;
+            let abs_x_mg = sensor.acceleration().unwrap().x_mg().abs();
+            let abs_x = abs_x_mg as f32 / 1000.0;
+            // The blocking display has no greyscale, the partly lit top of the bar shows fully
+            display.show(&mut timer, meter.update(abs_x_mg), FRAME_MS);
 
             if abs_x > THRESHOLD && !measure {
                 rprintln!("Enough Force: {}g", abs_x);
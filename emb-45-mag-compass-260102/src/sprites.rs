@@ -1,64 +1,68 @@
 
-pub const S0: [[u8; 5]; 5] = [
-    [0, 0, 1, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-];
+// One dot on the edge per direction, clockwise from the top
 
-pub const S1: [[u8; 5]; 5] = [
-    [0, 0, 0, 0, 1],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-];
+use led_matrix::{animation::Image, sprite};
 
-pub const S2: [[u8; 5]; 5] = [
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 1],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-];
+pub const S0: Image = sprite!("
+    ..1..
+    .....
+    .....
+    .....
+    .....
+");
 
-pub const S3: [[u8; 5]; 5] = [
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 1],
-];
+pub const S1: Image = sprite!("
+    ....1
+    .....
+    .....
+    .....
+    .....
+");
 
-pub const S4: [[u8; 5]; 5] = [
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 1, 0, 0],
-];
+pub const S2: Image = sprite!("
+    .....
+    .....
+    ....1
+    .....
+    .....
+");
 
-pub const S5: [[u8; 5]; 5] = [
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [1, 0, 0, 0, 0],
-];
+pub const S3: Image = sprite!("
+    .....
+    .....
+    .....
+    .....
+    ....1
+");
 
-pub const S6: [[u8; 5]; 5] = [
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [1, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-];
+pub const S4: Image = sprite!("
+    .....
+    .....
+    .....
+    .....
+    ..1..
+");
 
-pub const S7: [[u8; 5]; 5] = [
-    [1, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-];
+pub const S5: Image = sprite!("
+    .....
+    .....
+    .....
+    .....
+    1....
+");
+
+pub const S6: Image = sprite!("
+    .....
+    .....
+    1....
+    .....
+    .....
+");
+
+pub const S7: Image = sprite!("
+    1....
+    .....
+    .....
+    .....
+    .....
+");
@@ -2,59 +2,84 @@
 use core::cell::RefCell;
 use cortex_m::interrupt::Mutex;
 use microbit::{
-    display::nonblocking::{Display, GreyscaleImage}, 
+    display::nonblocking::Display, 
     gpio::DisplayPins, 
     hal::{
         Rtc, Timer, clocks::Clocks, rtc
     }, 
     pac::{self, RTC0, TIMER1, interrupt}
 };
+use led_matrix::animation::{Animation, Animator, Image, Keyframe, Playback};
 use tiny_led_matrix::Render;
 
-fn cross_image(inner_brightness: u8, outer_brightness: u8) -> GreyscaleImage {
+fn cross_image(inner_brightness: u8, outer_brightness: u8) -> Image {
     let b = inner_brightness;
     let c = outer_brightness;
-    GreyscaleImage::new(&[
+    [
         [b, b, 0, b, b],
         [b, 7, 0, 7, b],
         [c, c, 7, c, c],
         [b, 7, 0, 7, b],
         [b, b, 0, b, b],
-    ])
+    ]
 }
 
-fn right_arrow_image(inner_brightness: u8) -> GreyscaleImage {
+fn right_arrow_image(inner_brightness: u8) -> Image {
     let b = inner_brightness;
-    GreyscaleImage::new(&[
+    [
         [0, 0, b, 0, 0],
         [0, 0, 0, b, 0],
         [7, 7, 7, 7, 7],
         [0, 0, 0, b, 0],
         [0, 0, b, 0, 0],
-    ])
+    ]
 }
 
-fn left_arrow_image(inner_brightness: u8) -> GreyscaleImage {
+fn left_arrow_image(inner_brightness: u8) -> Image {
     let b = inner_brightness;
-    GreyscaleImage::new(&[
+    [
         [0, 0, b, 0, 0],
         [0, b, 0, 0, 0],
         [7, 7, 7, 7, 7],
         [0, b, 0, 0, 0],
         [0, 0, b, 0, 0],
-    ])
+    ]
 }
 
-#[derive(Clone, Copy)]
-pub enum ImageState {
-    Cross,
-    Right,
-    Left,
+// One round of the fade, the inner part goes dark first and the outer part follows
+const FADE_TICKS: u32 = 13;
+
+fn inner_brightness(tick: u32) -> u8 {
+    match tick {
+        0..=8 => 9 - tick as u8,
+        _ => 0,
+    }
 }
 
+fn outer_brightness(tick: u32) -> u8 {
+    match tick {
+        0..=4 => 0,
+        _ => 9 - (tick / 3) as u8,
+    }
+}
+
+pub static CROSS: Animation = Animation::new(
+    &[Keyframe::procedural(|tick| cross_image(inner_brightness(tick), outer_brightness(tick)), FADE_TICKS)],
+    Playback::Loop,
+);
+pub static RIGHT_ARROW: Animation = Animation::new(
+    &[Keyframe::procedural(|tick| right_arrow_image(inner_brightness(tick)), FADE_TICKS)],
+    Playback::Loop,
+);
+pub static LEFT_ARROW: Animation = Animation::new(
+    &[Keyframe::procedural(|tick| left_arrow_image(inner_brightness(tick)), FADE_TICKS)],
+    Playback::Loop,
+);
+
 pub static SHARED_DISPLAY: Mutex<RefCell<Option<Display<TIMER1>>>> = Mutex::new(RefCell::new(None));
 pub static SHARED_TIMER: Mutex<RefCell<Option<Rtc<RTC0>>>> = Mutex::new(RefCell::new(None));
-pub static SHARED_IMAGE_STATE: Mutex<RefCell<Option<ImageState>>> = Mutex::new(RefCell::new(None));
+// The RTC0 tick moves it on, main only picks the animation through play
+static SHARED_ANIMATOR: Mutex<RefCell<Option<Animator>>> = Mutex::new(RefCell::new(None));
 
 pub fn init_display(timer: pac::TIMER1, pins: DisplayPins, clock: pac::CLOCK, rtc: pac::RTC0) {
     Clocks::new(clock).start_lfclk();
@@ -69,7 +94,7 @@ pub fn init_display(timer: pac::TIMER1, pins: DisplayPins, clock: pac::CLOCK, rt
     cortex_m::interrupt::free(|cs| {
         SHARED_DISPLAY.borrow(cs).replace(Some(display));
         SHARED_TIMER.borrow(cs).replace(Some(rtc0));
-        SHARED_IMAGE_STATE.borrow(cs).replace(Some(ImageState::Cross));
+        SHARED_ANIMATOR.borrow(cs).replace(Some(Animator::new(CROSS)));
     });
 
     unsafe { pac::NVIC::unmask(pac::interrupt::TIMER1) };
@@ -101,41 +126,30 @@ fn TIMER1() {
     })
 }
 
+// Switches to another animation, the one already playing keeps going.
+// Statics only, is_playing goes by where the frames are.
+pub fn play(animation: &'static Animation) {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(animator) = SHARED_ANIMATOR.borrow(cs).borrow_mut().as_mut()
+            && !animator.is_playing(animation)
+        {
+            animator.play(*animation);
+            show_image(animator);
+        }
+    })
+}
+
 #[interrupt]
 fn RTC0() {
-    static mut STEP: u8 = 0;
-    let mut local_image_state = ImageState::Cross;
-
     cortex_m::interrupt::free(|cs| {
         if let Some(rtc) = SHARED_TIMER.borrow(cs).borrow_mut().as_mut() {
             rtc.reset_event(rtc::RtcInterrupt::Tick);
         }
 
-        if let Some(state) = SHARED_IMAGE_STATE.borrow(cs).borrow().as_ref() {
-            local_image_state = *state;
+        if let Some(animator) = SHARED_ANIMATOR.borrow(cs).borrow_mut().as_mut()
+            && animator.tick()
+        {
+            show_image(animator);
         }
     });
-
-    let inner_brightness = match *STEP {
-        0..=8 => 9 - *STEP,
-        9..=12 => 0,
-        _ => unreachable!(),
-    };
-
-    let outer_brightness = match *STEP {
-        0..=4 => 0,
-        5..=12 => 9 - (*STEP  / 3),
-        _ => unreachable!(),
-    };
-
-    match local_image_state {
-        ImageState::Cross => show_image(&cross_image(inner_brightness, outer_brightness)),
-        ImageState::Right => show_image(&right_arrow_image(inner_brightness)),
-        ImageState::Left => show_image(&left_arrow_image(inner_brightness)),
-    }
-
-    *STEP += 1;
-    if *STEP == 13 {
-        *STEP = 0
-    };
 }
@@ -13,7 +13,7 @@ mod game;
 use game::rng::Prng;
 use game::snake::Snake;
 
-use crate::{display::{ImageState, SHARED_IMAGE_STATE}, game::movement::Turn};
+use crate::game::movement::Turn;
 
 mod controls;
 mod display;
@@ -40,14 +40,11 @@ fn main() -> ! {
         asm::wfi();
 
         let current_turn = controls::get_turn(false);
-        let image_state = match current_turn {
-            Turn::None => ImageState::Cross,
-            Turn::Right => ImageState::Right,
-            Turn::Left => ImageState::Left,
-        };
         rprintln!("Turn is: {:?}", current_turn);
-        cortex_m::interrupt::free(|cs| {
-            SHARED_IMAGE_STATE.borrow(cs).replace(Some(image_state));
-        })
+        display::play(match current_turn {
+            Turn::None => &display::CROSS,
+            Turn::Right => &display::RIGHT_ARROW,
+            Turn::Left => &display::LEFT_ARROW,
+        });
     }
 }
\ No newline at end of file
@@ -0,0 +1,10 @@
+use std::{env, fs, path::PathBuf};
+
+// Puts our memory.x in front of the one from the hal, it keeps the high score pages free
+fn main() {
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::copy("memory.x", out.join("memory.x")).unwrap();
+    println!("cargo:rustc-link-search={}", out.display());
+    println!("cargo:rerun-if-changed=memory.x");
+    println!("cargo:rerun-if-changed=build.rs");
+}
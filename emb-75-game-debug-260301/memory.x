@@ -0,0 +1,8 @@
+/* nRF52833 without a soft device. The last two 4 KiB pages of the flash hold the high scores
+   (see src/highscore.rs), so they are left out here and nothing gets linked onto them. */
+MEMORY
+{
+  /* NOTE K = KiBi = 1024 bytes */
+  FLASH : ORIGIN = 0x00000000, LENGTH = 504K
+  RAM : ORIGIN = 0x20000000, LENGTH = 128K
+}
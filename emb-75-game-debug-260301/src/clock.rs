@@ -0,0 +1,59 @@
+use core::cell::RefCell;
+use cortex_m::interrupt::Mutex;
+use microbit::{
+    hal::{Rtc, rtc::RtcInterrupt},
+    pac::{self, RTC1, interrupt},
+};
+
+// 32768 Hz / 32, close enough to a millisecond per tick for timestamps
+const PRESCALER: u32 = 31;
+const TICK_HZ: u64 = 32768 / (PRESCALER as u64 + 1);
+
+// The counter only has 24 bits, the overflows counted in the interrupt make up the rest
+struct Clock {
+    rtc: Rtc<RTC1>,
+    overflows: u32,
+}
+
+static SHARED_CLOCK: Mutex<RefCell<Option<Clock>>> = Mutex::new(RefCell::new(None));
+
+// Needs the low frequency clock, init_display starts it
+pub fn init_clock(rtc: pac::RTC1) {
+    let mut rtc = Rtc::new(rtc, PRESCALER).unwrap();
+    rtc.enable_event(RtcInterrupt::Overflow);
+    rtc.enable_interrupt(RtcInterrupt::Overflow, None);
+    rtc.enable_counter();
+    cortex_m::interrupt::free(|cs| {
+        SHARED_CLOCK.borrow(cs).replace(Some(Clock { rtc, overflows: 0 }));
+    });
+    unsafe { pac::NVIC::unmask(pac::interrupt::RTC1) };
+}
+
+// Milliseconds since init_clock, wraps around like any u32 after about 49 days
+pub fn now_ms() -> u32 {
+    cortex_m::interrupt::free(|cs| {
+        SHARED_CLOCK.borrow(cs).borrow().as_ref().map_or(0, |clock| {
+            // The interrupt can not run in here, so an overflow may be pending. The counter may
+            // also overflow between the reads, so it is read again after the event: with the
+            // event set that second read is past the overflow for sure.
+            let counter = clock.rtc.get_counter();
+            let (pending, counter) = if clock.rtc.is_event_triggered(RtcInterrupt::Overflow) {
+                (1, clock.rtc.get_counter())
+            } else {
+                (0, counter)
+            };
+            let ticks = ((clock.overflows as u64 + pending) << 24) | counter as u64;
+            (ticks * 1000 / TICK_HZ) as u32
+        })
+    })
+}
+
+#[interrupt]
+fn RTC1() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(clock) = SHARED_CLOCK.borrow(cs).borrow_mut().as_mut() {
+            clock.rtc.reset_event(RtcInterrupt::Overflow);
+            clock.overflows = clock.overflows.wrapping_add(1);
+        }
+    })
+}
@@ -1,31 +1,50 @@
 use core::cell::RefCell;
 use cortex_m::interrupt::Mutex;
+use embedded_hal::{delay::DelayNs, digital::InputPin};
+use lsm303agr::{AccelMode, AccelOutputDataRate, Lsm303agr, interface::I2cInterface, mode::MagOneShot};
 use microbit::{
     board::Buttons, 
-    hal::gpiote::Gpiote,
-    pac::{self,interrupt},
+    hal::{gpiote::Gpiote, twim::{self, Twim}, uarte::{self, Baudrate, Parity, Uarte}},
+    pac::{self, interrupt, twim0::frequency::FREQUENCY_A},
 };
-use rtt_target::rprintln;
+use heapless::spsc::{Producer, Queue};
+use led_matrix::orientation::{Orientation, OrientationTracker};
+use snake_core::{
+    app::Action,
+    input::{KeyDecoder, Press, TurnSource, tilt_direction, turn_towards},
+    movement::{Direction, Turn},
+    turn_queue::{TurnEvent, TurnReader},
+};
+
+use crate::{clock, serial_setup::UartePort};
 
-use crate::game::movement::Turn;
+// About 20 degrees of tilt before the snake turns
+const TILT_THRESHOLD_MG: i32 = 350;
+// The display only turns when the board is held clearly on its side, for a few readings in a row
+const ORIENTATION_THRESHOLD_MG: i32 = 600;
+const ORIENTATION_SETTLE: u8 = 3;
+
+// Room for a few presses ahead of the game, one slot of the queue always stays free
+const TURN_QUEUE_LEN: usize = 8;
 
 static SHARED_GPIOTE: Mutex<RefCell<Option<Gpiote>>> = Mutex::new(RefCell::new(None));
-pub static SHARED_TURN: Mutex<RefCell<Option<Turn>>> = Mutex::new(RefCell::new(None));
+static SHARED_TURNS: Mutex<RefCell<Option<Producer<'static, TurnEvent, TURN_QUEUE_LEN>>>> = Mutex::new(RefCell::new(None));
 
 #[interrupt]
 fn GPIOTE() {
     cortex_m::interrupt::free(|cs| {
         if let Some(gpiote) = SHARED_GPIOTE.borrow(cs).borrow().as_ref() {
-            let a_pressed = gpiote.channel0().is_event_triggered();
-            let b_pressed = gpiote.channel1().is_event_triggered();
-
-            let turn = match (a_pressed, b_pressed) {
-                (true, false) => Turn::Left,
-                (false, true) => Turn::Right,
-                _ => Turn::None
-            };
-
-            SHARED_TURN.borrow(cs).replace(Some(turn));
+            let now = clock::now_ms();
+            if let Some(turns) = SHARED_TURNS.borrow(cs).borrow_mut().as_mut() {
+                // Both at once end up as a chord, TurnReader makes that one Press::AB.
+                // A full queue drops the press, the player is way ahead of the snake then.
+                if gpiote.channel0().is_event_triggered() {
+                    let _ = turns.enqueue(TurnEvent::new(Turn::Left, now));
+                }
+                if gpiote.channel1().is_event_triggered() {
+                    let _ = turns.enqueue(TurnEvent::new(Turn::Right, now));
+                }
+            }
 
             gpiote.channel0().reset_events();
             gpiote.channel1().reset_events();
@@ -33,7 +52,8 @@ fn GPIOTE() {
     })
 }
 
-pub fn init_buttons(board_gpiote: pac::GPIOTE, buttons: Buttons) {
+// The game loop reads the presses from the returned reader
+fn init_buttons(board_gpiote: pac::GPIOTE, buttons: Buttons) -> TurnReader<'static, TURN_QUEUE_LEN> {
     let button_a = buttons.button_a.into_pullup_input().degrade();
     let button_b = buttons.button_b.into_pullup_input().degrade();
 
@@ -46,25 +66,249 @@ pub fn init_buttons(board_gpiote: pac::GPIOTE, buttons: Buttons) {
     channel1.input_pin(&button_b).hi_to_lo().enable_interrupt();
     channel1.reset_events();
 
+    let queue = cortex_m::singleton!(: Queue<TurnEvent, TURN_QUEUE_LEN> = Queue::new()).unwrap();
+    let (producer, consumer) = queue.split();
 
     cortex_m::interrupt::free(|cs| {
         SHARED_GPIOTE.borrow(cs).replace(Some(gpiote));
-        SHARED_TURN.borrow(cs).replace(Some(Turn::None));
+        SHARED_TURNS.borrow(cs).replace(Some(producer));
     });
 
     unsafe { pac::NVIC::unmask(pac::interrupt::GPIOTE) };
     pac::NVIC::unpend(pac::interrupt::GPIOTE);
+
+    TurnReader::new(consumer)
 }
 
-pub fn get_turn(reset: bool) -> Turn {
-    let mut turn = Turn::None;
-    cortex_m::interrupt::free(|cs| {
-        if let Some(inner_turn) = SHARED_TURN.borrow(cs).borrow().as_ref() {
-            turn = *inner_turn;
+// The buttons. The App sees every press first for the menus, during a game it passes the
+// steering ones on to here.
+pub struct ButtonTurns {
+    presses: TurnReader<'static, TURN_QUEUE_LEN>,
+    passed: Turn,
+}
+
+impl ButtonTurns {
+    fn new(presses: TurnReader<'static, TURN_QUEUE_LEN>) -> Self {
+        Self { presses, passed: Turn::None }
+    }
+
+    // One queued button press per call, see TurnReader for what gets dropped
+    pub fn next_press(&mut self) -> Option<Press> {
+        self.presses.next_press(clock::now_ms())
+    }
+
+    // What the App made of this step's press
+    pub fn pass(&mut self, action: Action) {
+        self.passed = match action {
+            Action::Steer(turn) => turn,
+            _ => Turn::None,
+        };
+    }
+}
+
+impl TurnSource for ButtonTurns {
+    fn next_turn(&mut self, _heading: Direction) -> Turn {
+        core::mem::replace(&mut self.passed, Turn::None)
+    }
+}
+
+type Accelerometer = Lsm303agr<I2cInterface<Twim<pac::TWIM0>>, MagOneShot>;
+
+// The accelerometer: which way is down turns the display around, and with tilt steering the
+// way the board leans steers the snake
+pub struct Motion {
+    sensor: Accelerometer,
+    tracker: OrientationTracker,
+    tilt: Option<Direction>,
+}
+
+impl Motion {
+    pub fn new(twim: pac::TWIM0, pins: twim::Pins, delay: &mut impl DelayNs) -> Self {
+        let i2c = Twim::new(twim, pins, FREQUENCY_A::K100);
+        let mut sensor = Lsm303agr::new_with_i2c(i2c);
+        sensor.init().unwrap();
+        sensor.set_accel_mode_and_odr(delay, AccelMode::Normal, AccelOutputDataRate::Hz50).unwrap();
+        Self { sensor, tracker: OrientationTracker::new(ORIENTATION_THRESHOLD_MG, ORIENTATION_SETTLE), tilt: None }
+    }
+
+    fn update(&mut self) {
+        if self.sensor.accel_status().unwrap().xyz_new_data() {
+            let data = self.sensor.acceleration().unwrap();
+            // The sensor y axis points to the top edge, tilt_direction wants it pointing down.
+            // Lying face up reads about -1000 on z, face down about +1000.
+            let (x, y, z) = (data.x_mg(), -data.y_mg(), data.z_mg());
+            self.tilt = tilt_direction(x, y, TILT_THRESHOLD_MG);
+            self.tracker.update(x, y, z);
+        }
+    }
+
+    fn orientation(&self) -> Orientation {
+        self.tracker.orientation()
+    }
+}
+
+// Tilting the board steers the snake the way it leans, on the reading Motion took this step
+pub struct TiltTurns<'a> {
+    motion: &'a Motion,
+}
+
+impl<'a> TiltTurns<'a> {
+    pub fn new(motion: &'a Motion) -> Self {
+        Self { motion }
+    }
+}
+
+impl TurnSource for TiltTurns<'_> {
+    fn next_turn(&mut self, heading: Direction) -> Turn {
+        // The way the board leans, as seen on the turned display
+        let inverse = self.motion.orientation().inverse();
+        self.motion.tilt.map_or(Turn::None, |direction| turn_towards(heading, turn_direction(inverse, direction)))
+    }
+}
+
+fn turn_direction(orientation: Orientation, direction: Direction) -> Direction {
+    let offset = match direction {
+        Direction::Up => (0, -1),
+        Direction::Right => (1, 0),
+        Direction::Down => (0, 1),
+        Direction::Left => (-1, 0),
+    };
+    match orientation.map_offset(offset.0, offset.1) {
+        (0, -1) => Direction::Up,
+        (1, 0) => Direction::Right,
+        (0, 1) => Direction::Down,
+        _ => Direction::Left,
+    }
+}
+
+// Left and right as seen on the display, on a mirrored one the snake turns the other way than
+// it seems to
+pub struct Mirrored<S> {
+    source: S,
+    mirrored: bool,
+}
+
+impl<S: TurnSource> Mirrored<S> {
+    pub fn new(source: S, orientation: Orientation) -> Self {
+        Self { source, mirrored: orientation.mirrored }
+    }
+}
+
+impl<S: TurnSource> TurnSource for Mirrored<S> {
+    fn next_turn(&mut self, heading: Direction) -> Turn {
+        match (self.mirrored, self.source.next_turn(heading)) {
+            (true, Turn::Left) => Turn::Right,
+            (true, Turn::Right) => Turn::Left,
+            (_, turn) => turn,
         }
-        if reset {
-            SHARED_TURN.borrow(cs).replace(Some(Turn::None));
+    }
+}
+
+// a/d or the arrow keys from a terminal on the USB serial port, the last key during a step wins
+pub struct SerialTurns {
+    port: UartePort<pac::UARTE0>,
+    keys: KeyDecoder,
+}
+
+impl SerialTurns {
+    pub fn new(uarte: pac::UARTE0, pins: uarte::Pins) -> Self {
+        let serial = Uarte::new(uarte, pins, Parity::EXCLUDED, Baudrate::BAUD115200);
+        let port = UartePort::new(
+            serial,
+            cortex_m::singleton!(: [u8; 1] = [0; 1]).unwrap(),
+            cortex_m::singleton!(: [u8; 1] = [0; 1]).unwrap(),
+        );
+        Self { port, keys: KeyDecoder::new() }
+    }
+}
+
+impl TurnSource for SerialTurns {
+    fn next_turn(&mut self, _heading: Direction) -> Turn {
+        let mut turn = Turn::None;
+        while let Ok(Some(byte)) = self.port.try_read() {
+            if let Some(key_turn) = self.keys.feed(byte) {
+                turn = key_turn;
+            }
         }
-    });
-    turn
+        turn
+    }
+}
+
+// What steers the snake, the buttons always drive the menus and pause
+pub enum Steering {
+    Buttons,
+    Tilt,
+    Serial(SerialTurns),
+}
+
+pub struct Controls {
+    buttons: ButtonTurns,
+    steering: Steering,
+    motion: Motion,
+    versus: bool,
+    // The USB serial port when it is not steering
+    spare_uart: Option<(pac::UARTE0, uarte::Pins)>,
+}
+
+impl Controls {
+    // Both buttons held at reset ask for a game against another board
+    pub fn versus(&self) -> bool {
+        self.versus
+    }
+
+    // Once, and only if the serial port is not taken for steering
+    pub fn take_uart(&mut self) -> Option<(pac::UARTE0, uarte::Pins)> {
+        self.spare_uart.take()
+    }
+
+    pub fn next_press(&mut self) -> Option<Press> {
+        self.buttons.next_press()
+    }
+
+    // Reads the accelerometer, call it every step and show everything turned this way
+    pub fn update_orientation(&mut self) -> Orientation {
+        self.motion.update();
+        self.motion.orientation()
+    }
+
+    // action is what the App made of this step's press, the buttons steer with that.
+    // Turns are meant as seen on the display, which may be turned around.
+    pub fn steer(&mut self, heading: Direction, action: Action) -> Turn {
+        self.buttons.pass(action);
+        let orientation = self.motion.orientation();
+        let source: &mut dyn TurnSource = match &mut self.steering {
+            Steering::Buttons => &mut Mirrored::new(&mut self.buttons, orientation),
+            Steering::Tilt => &mut TiltTurns::new(&self.motion),
+            Steering::Serial(serial) => &mut Mirrored::new(serial, orientation),
+        };
+        source.next_turn(heading)
+    }
+}
+
+// Picked at reset: hold A for tilt, B for serial, nothing (or both for versus) for the buttons.
+// The buttons get their interrupt either way, they are only read before that. The accelerometer
+// is always on, it turns the display.
+pub fn init_controls(
+    mut buttons: Buttons,
+    gpiote: pac::GPIOTE,
+    twim: pac::TWIM0,
+    i2c_pins: twim::Pins,
+    uarte: pac::UARTE0,
+    uart_pins: uarte::Pins,
+    delay: &mut impl DelayNs,
+) -> Controls {
+    let a_held = buttons.button_a.is_low().unwrap();
+    let b_held = buttons.button_b.is_low().unwrap();
+    let buttons = ButtonTurns::new(init_buttons(gpiote, buttons));
+    let motion = Motion::new(twim, i2c_pins, delay);
+    let mut spare_uart = Some((uarte, uart_pins));
+    let steering = match (a_held, b_held) {
+        (true, false) => Steering::Tilt,
+        (false, true) => {
+            let (uarte, uart_pins) = spare_uart.take().unwrap();
+            Steering::Serial(SerialTurns::new(uarte, uart_pins))
+        },
+        _ => Steering::Buttons,
+    };
+    Controls { buttons, steering, motion, versus: a_held && b_held, spare_uart }
 }
@@ -0,0 +1,75 @@
+use core::{cell::RefCell, mem::MaybeUninit, panic::PanicInfo, sync::atomic::{Ordering, compiler_fence}};
+use cortex_m::interrupt::Mutex;
+use rtt_target::{rprint, rprintln};
+use snake_core::{Game, snapshot::snapshot_len};
+
+// Replaces panic-rtt-target: prints the panic the same way, then keeps the last game snapshot
+// in RAM that is not cleared at startup, so it is still there after pressing reset.
+// Gone after a power cycle, but flashing a page from the panic handler is asking for trouble.
+
+const SNAPSHOT_LEN: usize = snapshot_len(5, 5);
+// Anything else in there is left over from power on
+const CRASH_MARKER: u32 = 0x5eed_dead;
+
+#[repr(C)]
+struct CrashDump {
+    marker: u32,
+    line: u32,
+    len: u32,
+    snapshot: [u8; SNAPSHOT_LEN],
+}
+
+#[unsafe(link_section = ".uninit.CRASH_DUMP")]
+static mut CRASH_DUMP: MaybeUninit<CrashDump> = MaybeUninit::uninit();
+
+static LAST_SNAPSHOT: Mutex<RefCell<([u8; SNAPSHOT_LEN], usize)>> = Mutex::new(RefCell::new(([0; SNAPSHOT_LEN], 0)));
+
+// Called before every step, a panic in the step then leaves the state it started from
+pub fn record(game: &Game) {
+    cortex_m::interrupt::free(|cs| {
+        let mut last = LAST_SNAPSHOT.borrow(cs).borrow_mut();
+        last.1 = game.snapshot(&mut last.0).unwrap_or(0);
+    })
+}
+
+// Prints the dump left by a panic before the last reset, if any.
+// Paste the hex into `cargo run -- --crash` in snake-sim to see the board.
+pub fn report_last_crash() {
+    // Only read here and written by the panic handler, never at the same time.
+    // Volatile so the compiler does not assume it knows what is in there.
+    let dump = (&raw mut CRASH_DUMP).cast::<CrashDump>();
+    let CrashDump { marker, line, len, snapshot } = unsafe { dump.read_volatile() };
+    if marker != CRASH_MARKER {
+        return;
+    }
+    rprintln!("Crashed at line {} last time", line);
+    if let Some(snapshot) = snapshot.get(..len as usize).filter(|s| !s.is_empty()) {
+        rprint!("crash ");
+        for byte in snapshot {
+            rprint!("{:02x}", byte);
+        }
+        rprintln!();
+    }
+    unsafe { (&raw mut (*dump).marker).write_volatile(0) };
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    cortex_m::interrupt::disable();
+    rprintln!("{}", info);
+
+    // The panic may come from inside record, then there is no snapshot to save
+    let last = cortex_m::interrupt::free(|cs| LAST_SNAPSHOT.borrow(cs).try_borrow().ok().map(|last| *last));
+    let (snapshot, len) = last.unwrap_or(([0; SNAPSHOT_LEN], 0));
+    let dump = CrashDump {
+        marker: CRASH_MARKER,
+        line: info.location().map_or(0, |location| location.line()),
+        len: len as u32,
+        snapshot,
+    };
+    unsafe { (&raw mut CRASH_DUMP).write(MaybeUninit::new(dump)) };
+
+    loop {
+        compiler_fence(Ordering::SeqCst);
+    }
+}
@@ -1,19 +1,24 @@
 
 use core::cell::RefCell;
 use cortex_m::interrupt::Mutex;
+use led_matrix::{animation::Image, mirror::MirrorFrame, orientation::Orientation};
 use microbit::{
-    display::nonblocking::{Display, GreyscaleImage}, 
+    display::nonblocking::Display, 
     gpio::DisplayPins, 
-    hal::{
-        Rtc, Timer, clocks::Clocks, rtc
-    }, 
-    pac::{self, RTC0, TIMER1, interrupt}
+    hal::{clocks::Clocks, uarte::{self, Baudrate, Parity, Uarte}}, 
+    pac::{self, TIMER1, UARTE0, interrupt}
 };
 use tiny_led_matrix::Render;
 
+use crate::{clock, serial_setup::UartePort};
+
+// Unchanged frames go out again this often, so a viewer started late still gets a picture
+const MIRROR_REPEAT_MS: u32 = 1000;
+
 pub static SHARED_DISPLAY: Mutex<RefCell<Option<Display<TIMER1>>>> = Mutex::new(RefCell::new(None));
+static SHARED_ORIENTATION: Mutex<RefCell<Orientation>> = Mutex::new(RefCell::new(Orientation::UPRIGHT));
 
-pub fn init_display(timer: pac::TIMER1, pins: DisplayPins, clock: pac::CLOCK, rtc: pac::RTC0) {
+pub fn init_display(timer: pac::TIMER1, pins: DisplayPins, clock: pac::CLOCK) {
     Clocks::new(clock).start_lfclk();
 
     let display = Display::new(timer, pins);
@@ -25,20 +30,63 @@ pub fn init_display(timer: pac::TIMER1, pins: DisplayPins, clock: pac::CLOCK, rt
     unsafe { pac::NVIC::unmask(pac::interrupt::TIMER1) };
 }
 
+// Taken from the next show_image on
+pub fn set_orientation(orientation: Orientation) {
+    cortex_m::interrupt::free(|cs| {
+        SHARED_ORIENTATION.borrow(cs).replace(orientation);
+    });
+}
+
+// Drawn with the USB connector at the top, turned to how the board is held on the way out.
+// The mirror gets it as drawn.
 pub fn show_image(image: &impl Render) {
     cortex_m::interrupt::free(|cs| {
         if let Some(display) = SHARED_DISPLAY.borrow(cs).borrow_mut().as_mut() {
-            display.show(image);
+            let orientation = *SHARED_ORIENTATION.borrow(cs).borrow();
+            display.show(&orientation.render(image));
         }
-    })
+    });
+    mirror(core::array::from_fn(|y| core::array::from_fn(|x| image.brightness_at(x, y))));
+}
+
+// Every frame shown also goes out over the serial port, snake-sim --mirror draws them on the host
+struct Mirror {
+    port: UartePort<UARTE0>,
+    sequence: u8,
+    last: Option<(Image, u32)>,
 }
 
-pub fn clear_screen() {
+static SHARED_MIRROR: Mutex<RefCell<Option<Mirror>>> = Mutex::new(RefCell::new(None));
+
+pub fn init_mirror(uarte: UARTE0, pins: uarte::Pins) {
+    let serial = Uarte::new(uarte, pins, Parity::EXCLUDED, Baudrate::BAUD115200);
+    let port = UartePort::new(
+        serial,
+        cortex_m::singleton!(: [u8; 1] = [0; 1]).unwrap(),
+        cortex_m::singleton!(: [u8; 1] = [0; 1]).unwrap(),
+    );
+    let mirror = Mirror { port, sequence: 0, last: None };
     cortex_m::interrupt::free(|cs| {
-        if let Some(display) = SHARED_DISPLAY.borrow(cs).borrow_mut().as_mut() {
-            display.clear();
-        }
-    })
+        SHARED_MIRROR.borrow(cs).replace(Some(mirror));
+    });
+}
+
+fn mirror(image: Image) {
+    // Taken out while sending, a frame takes over a millisecond at 115200 baud
+    let Some(mut mirror) = cortex_m::interrupt::free(|cs| SHARED_MIRROR.borrow(cs).take()) else {
+        return;
+    };
+    let now = clock::now_ms();
+    let unchanged = mirror.last.is_some_and(|(last, sent_ms)| last == image && now.wrapping_sub(sent_ms) < MIRROR_REPEAT_MS);
+    if !unchanged {
+        // Nobody listening is fine, the frame is lost either way
+        let _ = mirror.port.write_all(&MirrorFrame::new(mirror.sequence, image).encode());
+        mirror.sequence = mirror.sequence.wrapping_add(1);
+        mirror.last = Some((image, now));
+    }
+    cortex_m::interrupt::free(|cs| {
+        SHARED_MIRROR.borrow(cs).replace(Some(mirror));
+    });
 }
 
 #[interrupt]
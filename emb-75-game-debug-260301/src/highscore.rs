@@ -0,0 +1,37 @@
+use microbit::{hal::nvmc::Nvmc, pac};
+use rtt_target::rprintln;
+use snake_core::highscore::HighScoreStore;
+
+// The last two 4 KiB pages of the 512 KiB flash, memory.x keeps the firmware out of them
+const HIGH_SCORE_PAGES: usize = 0x0007_e000;
+const PAGE_SIZE: usize = 4 * 1024;
+
+pub type Store = HighScoreStore<Nvmc<pac::NVMC>>;
+
+pub fn init_high_scores(nvmc: pac::NVMC) -> Store {
+    // Safe as long as nothing else uses these pages, the Nvmc is the only one touching them from now on
+    let storage = unsafe { core::slice::from_raw_parts_mut(HIGH_SCORE_PAGES as *mut u8, 2 * PAGE_SIZE) };
+    let mut store = HighScoreStore::new(Nvmc::new(nvmc, storage), 0);
+    match store.load() {
+        Ok(table) => rprintln!("High scores: {:?}", table.scores()),
+        Err(e) => rprintln!("Could not read high scores: {:?}", e),
+    }
+    store
+}
+
+// True if the score is the new best
+pub fn submit(store: &mut Store, score: u16) -> bool {
+    match store.submit(score) {
+        Ok((table, place)) => {
+            rprintln!("High scores: {:?}", table.scores());
+            if let Some(place) = place {
+                rprintln!("Score {} is number {} in the table", score, place + 1);
+            }
+            place == Some(0)
+        },
+        Err(e) => {
+            rprintln!("Could not save high score: {:?}", e);
+            false
+        }
+    }
+}
@@ -1,59 +1,278 @@
 #![no_std]
 #![no_main]
 
-use cortex_m::asm;
+use core::fmt::Write;
 use cortex_m_rt::entry;
 use embedded_hal::delay::DelayNs;
 use microbit::{
     board, 
-    display::nonblocking::{BitImage, GreyscaleImage}, 
-    hal::{Rng, Timer}
+    display::nonblocking::GreyscaleImage, 
+    hal::Rng,
+};
+use led_matrix::{
+    animation::Image,
+    scroll::ScrollingText,
+    sprite,
+    transition::{Direction, Effect, Transition},
 };
-use panic_rtt_target as _;
 use rtt_target::{rtt_init_print, rprintln};
+use snake_core::{
+    Game,
+    app::{Action, App, AppState, Difficulty},
+    autopilot::{Autopilot, Strategy},
+    level::LEVELS,
+    movement::GameStatus,
+    replay::ReplayLog,
+    rules::GameRules,
+    sound::SoundEffect,
+};
 
-mod game;
-
-use crate::game::{Game, movement::GameStatus};
-
+mod clock;
+mod crash;
 mod controls;
 mod display;
+mod highscore;
+mod serial_setup;
+mod speaker;
+mod ticks;
+mod versus;
+
+// One byte per step, enough for a long game at full speed
+const REPLAY_LEN: usize = 1024;
+const RULES: GameRules = GameRules::LEVELS;
+const SCROLL_STEP_MS: u32 = 120;
+// The demo plays on the open board, where the Hamiltonian autopilot can not lose
+const DEMO_RULES: GameRules = GameRules::CLASSIC;
+const DEMO_STEP_MS: u32 = 250;
+// How often the buttons are checked while nothing moves
+const IDLE_POLL_MS: u32 = 50;
+const TRANSITION_FRAME_MS: u32 = 60;
+const NEW_RECORD_MS: u32 = 1000;
+
+const BLANK: Image = [[0; 5]; 5];
+
+// Shown at the end of a game that beat the best score so far
+const NEW_RECORD: Image = sprite!("
+    ..#..
+    #####
+    .###.
+    .#.#.
+    #...#
+");
+
+const PAUSED: Image = sprite!("
+    .....
+    .#.#.
+    .#.#.
+    .#.#.
+    .....
+");
 
 #[entry]
 fn main() -> ! {
     rtt_init_print!();
+    crash::report_last_crash();
     let board = board::Board::take().unwrap();
-    let mut timer = Timer::new(board.TIMER0).into_periodic();
     let mut hardware_rng = Rng::new(board.RNG);
-    let mut game = Game::new(&mut hardware_rng);
+    let mut app = App::new(Difficulty::Normal);
+    // The title screen runs the demo until someone presses B
+    let mut demo: Game = Game::new(hardware_rng.random_u32(), DEMO_RULES);
+    let mut autopilot = Autopilot::new(Strategy::Hamiltonian);
+    let seed = hardware_rng.random_u32();
+    let mut game: Game = Game::with_levels(seed, RULES, &LEVELS);
+    let mut replay_log: ReplayLog<REPLAY_LEN> = ReplayLog::new(seed);
+    let mut ending: Option<GameOverScreen> = None;
 
-    display::init_display(board.TIMER1, board.display_pins, board.CLOCK, board.RTC0);
-    controls::init_buttons(board.GPIOTE, board.buttons);
+    display::init_display(board.TIMER1, board.display_pins, board.CLOCK);
+    clock::init_clock(board.RTC1);
+    let mut ticks = ticks::init_ticks(board.RTC0);
+    let mut controls = controls::init_controls(
+        board.buttons,
+        board.GPIOTE,
+        board.TWIM0,
+        board.i2c_internal.into(),
+        board.UARTE0,
+        board.uart.into(),
+        &mut ticks,
+    );
+    // UARTE0 mirrors the display unless it steers, versus runs on UARTE1 so both work at once
+    if let Some((uarte, uart_pins)) = controls.take_uart() {
+        display::init_mirror(uarte, uart_pins);
+    }
+    let mut high_scores = highscore::init_high_scores(board.NVMC);
+    speaker::init_speaker(board.TIMER2, board.speaker_pin);
+
+    if controls.versus() {
+        versus::run(board.UARTE1, board.edge, &mut controls, &mut hardware_rng, &mut ticks);
+    }
 
     loop {
-        loop {
-            let image = GreyscaleImage::new(&game.game_matrix(6, 3, 9));
-            display::show_image(&image);
-            rprintln!("{:?}", game.status);
-            rprintln!("{:?}", game.score);
-            timer.delay_ms(game.step_len_ms());
-            
-            match game.status {
-                GameStatus::Ongoing => game.step(controls::get_turn(true)),
-                _ => {
-                    for _ in 0..3 {
-                        display::clear_screen();
-                        timer.delay_ms(200);
-                        display::show_image(&image);
-                        timer.delay_ms(200);
-                    }
-                    display::clear_screen();
-                    display::show_image(&BitImage::new(&game.score_matrix()));
-                    timer.delay_ms(200);
-                    break;
+        display::set_orientation(controls.update_orientation());
+        match app.state() {
+            AppState::Title => {
+                display::show_image(&GreyscaleImage::new(&demo.game_matrix(6, 3, 9, 1)));
+                ticks.delay_ms(DEMO_STEP_MS);
+            },
+            AppState::Playing => {
+                display::show_image(&GreyscaleImage::new(&game.game_matrix(6, 3, 9, 1)));
+                let curve = app.difficulty().speed_curve();
+                rprintln!("{:?} score {} level {}", game.status, game.score, curve.level(&game));
+                ticks.delay_ms(curve.step_len_ms(&game));
+            },
+            AppState::Paused => {
+                display::show_image(&GreyscaleImage::new(&PAUSED));
+                ticks.delay_ms(IDLE_POLL_MS);
+            },
+            AppState::GameOver => {
+                let ms = ending.as_mut().map_or(IDLE_POLL_MS, |ending| ending.show_next());
+                ticks.delay_ms(ms);
+            },
+        }
+
+        let press = controls.next_press();
+        // Presses while the game over screen still plays were meant for the snake, drop them
+        let ending_done = app.state() != AppState::GameOver || ending.as_ref().is_none_or(|ending| ending.is_done());
+        let action = press.filter(|_| ending_done).map_or(Action::Nothing, |press| app.press(press));
+        match action {
+            Action::ChangeDifficulty(difficulty) => {
+                let mut text: ScrollingText<16> = ScrollingText::new("");
+                write!(text, "{:?}", difficulty).unwrap();
+                scroll(&mut text, &mut ticks);
+                continue;
+            },
+            Action::StartGame(_) => {
+                // A fresh seed per game, so the log of each game starts from a known state
+                let seed = hardware_rng.random_u32();
+                game = Game::with_levels(seed, RULES, &LEVELS);
+                replay_log.reset(seed);
+                continue;
+            },
+            Action::ShowTitle => {
+                demo = Game::new(hardware_rng.random_u32(), DEMO_RULES);
+                autopilot = Autopilot::new(Strategy::Hamiltonian);
+                continue;
+            },
+            Action::Pause | Action::Resume => continue,
+            Action::Steer(_) | Action::Nothing => (),
+        }
+
+        match app.state() {
+            AppState::Title => {
+                demo.step(autopilot.next_turn(&demo));
+                // Round and round until someone wants to play
+                if demo.status != GameStatus::Ongoing {
+                    demo = Game::new(hardware_rng.random_u32(), DEMO_RULES);
+                    autopilot = Autopilot::new(Strategy::Hamiltonian);
+                }
+            },
+            AppState::Playing => {
+                let turn = controls.steer(game.snake().direction, action);
+                if replay_log.record(turn).is_err() {
+                    rprintln!("Replay log full, this game can not be replayed");
+                }
+                let (score, speed) = (game.score, game.speed());
+                crash::record(&game);
+                game.step(turn);
+                if let Some(effect) = SoundEffect::after_step(score, speed, &game) {
+                    speaker::play(effect);
                 }
-            }
+                if game.status != GameStatus::Ongoing {
+                    ending = Some(end_game(&game, &replay_log, &mut high_scores));
+                    app.game_over();
+                }
+            },
+            AppState::Paused | AppState::GameOver => (),
+        }
+    }
+}
+
+fn end_game(game: &Game, replay_log: &ReplayLog<REPLAY_LEN>, high_scores: &mut highscore::Store) -> GameOverScreen {
+    // Paste this line into ReplayLog::parse and replay it with RULES and LEVELS to play the game
+    // again on the host
+    rprintln!("{}", replay_log);
+    let new_record = highscore::submit(high_scores, game.score);
+    GameOverScreen::new(game, new_record)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ending {
+    Dissolve,
+    NewRecord,
+    Score,
+    Done,
+}
+
+// Dissolves the last frame, fades in NEW_RECORD if it was one, scrolls the score and slides the
+// score matrix in. One frame per tick so the main loop keeps sleeping in between.
+struct GameOverScreen {
+    ending: Ending,
+    transition: Option<Transition>,
+    score: Image,
+    new_record: bool,
+    text: ScrollingText<16>,
+}
+
+impl GameOverScreen {
+    fn new(game: &Game, new_record: bool) -> Self {
+        let mut text: ScrollingText<16> = ScrollingText::new("");
+        write!(text, "SCORE {}", game.score).unwrap();
+        let last_frame = game.game_matrix(6, 3, 9, 1);
+        Self {
+            ending: Ending::Dissolve,
+            transition: Some(Transition::new(last_frame, BLANK, Effect::Dissolve { seed: game.steps() }, 10)),
+            score: game.score_matrix().map(|row| row.map(|v| v * 9)),
+            new_record,
+            text,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.ending == Ending::Done && self.transition.is_none()
+    }
+
+    // Shows the next frame and returns how long it stays up
+    fn show_next(&mut self) -> u32 {
+        if let Some(frame) = self.transition.as_mut().and_then(Transition::next_frame) {
+            display::show_image(&GreyscaleImage::new(&frame));
+            return TRANSITION_FRAME_MS;
         }
-        game.reset();
+        self.transition = None;
+
+        match self.ending {
+            Ending::Dissolve if self.new_record => {
+                self.ending = Ending::NewRecord;
+                self.transition = Some(Transition::new(BLANK, NEW_RECORD, Effect::Crossfade, 9));
+                self.show_next()
+            },
+            Ending::Dissolve => {
+                self.ending = Ending::Score;
+                self.show_next()
+            },
+            // NEW_RECORD is still up from the fade
+            Ending::NewRecord => {
+                self.ending = Ending::Score;
+                NEW_RECORD_MS
+            },
+            Ending::Score if self.text.advance() => {
+                display::show_image(&self.text);
+                SCROLL_STEP_MS
+            },
+            Ending::Score => {
+                self.ending = Ending::Done;
+                self.transition = Some(Transition::new(BLANK, self.score, Effect::Slide(Direction::Up), 5));
+                self.show_next()
+            },
+            Ending::Done => {
+                display::show_image(&GreyscaleImage::new(&self.score));
+                IDLE_POLL_MS
+            },
+        }
+    }
+}
+
+fn scroll(text: &mut ScrollingText<16>, ticks: &mut impl DelayNs) {
+    while text.advance() {
+        display::show_image(text);
+        ticks.delay_ms(SCROLL_STEP_MS);
     }
-}
\ No newline at end of file
+}
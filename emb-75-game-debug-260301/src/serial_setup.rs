@@ -0,0 +1,29 @@
+use embedded_io::{Read, ReadReady, Write as _};
+use microbit::hal::uarte::{self, Instance, Uarte, UarteRx, UarteTx};
+
+pub struct UartePort<T: Instance>(UarteTx<T>, UarteRx<T>);
+
+impl<T: Instance> UartePort<T> {
+    // Each port needs buffers of its own, take them from a cortex_m::singleton! at the call site
+    pub fn new(serial: Uarte<T>, tx_buf: &'static mut [u8; 1], rx_buf: &'static mut [u8; 1]) -> UartePort<T> {
+        let (tx, rx) = serial.split(tx_buf, rx_buf).unwrap();
+        UartePort(tx, rx)
+    }
+}
+
+impl<T: Instance> UartePort<T> {
+    pub fn write_all(&mut self, bytes: &[u8]) -> Result<(), uarte::Error> {
+        self.0.write_all(bytes)?;
+        self.0.flush()
+    }
+
+    // Like read, but gives None instead of waiting when no byte has come in yet
+    pub fn try_read(&mut self) -> Result<Option<u8>, uarte::Error> {
+        if !self.1.read_ready()? {
+            return Ok(None);
+        }
+        let mut buf = [0u8; 1];
+        self.1.read(&mut buf)?;
+        Ok(Some(buf[0]))
+    }
+}
@@ -0,0 +1,73 @@
+use core::cell::RefCell;
+use cortex_m::interrupt::Mutex;
+use embedded_hal::digital::OutputPin;
+use microbit::{
+    hal::{
+        Timer,
+        gpio::{Disconnected, Level, Output, Pin, PushPull, p0::P0_00},
+    },
+    pac::{self, TIMER2, interrupt},
+};
+use snake_core::sound::{Player, SoundEffect};
+
+// Same square wave approach as the siren, but on its own timer so TIMER0 stays free for the game
+// and every edge is one short interrupt instead of a blocking delay
+struct Speaker {
+    pin: Pin<Output<PushPull>>,
+    timer: Timer<TIMER2>,
+    player: Player,
+}
+
+static SHARED_SPEAKER: Mutex<RefCell<Option<Speaker>>> = Mutex::new(RefCell::new(None));
+
+impl Speaker {
+    fn step(&mut self) {
+        self.timer.reset_event();
+        match self.player.next_edge() {
+            Some(edge) => {
+                if edge.high {
+                    self.pin.set_high().unwrap();
+                } else {
+                    self.pin.set_low().unwrap();
+                }
+                self.timer.start(edge.wait_us);
+            },
+            None => {
+                self.pin.set_low().unwrap();
+                self.timer.disable_interrupt();
+            }
+        }
+    }
+}
+
+#[interrupt]
+fn TIMER2() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(speaker) = SHARED_SPEAKER.borrow(cs).borrow_mut().as_mut() {
+            speaker.step();
+        }
+    })
+}
+
+pub fn init_speaker(timer: pac::TIMER2, speaker_pin: P0_00<Disconnected>) {
+    let pin = speaker_pin.into_push_pull_output(Level::Low).degrade();
+    let timer = Timer::new(timer);
+
+    cortex_m::interrupt::free(|cs| {
+        SHARED_SPEAKER.borrow(cs).replace(Some(Speaker { pin, timer, player: Player::new() }));
+    });
+
+    unsafe { pac::NVIC::unmask(pac::interrupt::TIMER2) };
+    pac::NVIC::unpend(pac::interrupt::TIMER2);
+}
+
+// Returns right away, the effect plays from the TIMER2 interrupt and cuts off any effect still playing
+pub fn play(effect: SoundEffect) {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(speaker) = SHARED_SPEAKER.borrow(cs).borrow_mut().as_mut() {
+            speaker.player.play(effect);
+            speaker.timer.enable_interrupt();
+            speaker.timer.start(1);
+        }
+    })
+}
@@ -0,0 +1,116 @@
+use core::cell::RefCell;
+use cortex_m::{asm, interrupt::Mutex};
+use embedded_hal::delay::DelayNs;
+use microbit::pac::{self, RTC0, interrupt};
+use rtt_target::rprintln;
+
+// Game ticks come from RTC0 compare events like in emb-76, the CPU sleeps in between.
+// No prescaler, so 32768 ticks a second.
+const TICK_HZ: u32 = 32768;
+const COUNTER_MASK: u32 = 0x00FF_FFFF;
+// CC has to be at least two ticks ahead of the counter or the event can be missed
+const MIN_TICKS: u32 = 2;
+// How often the idle percentage is printed
+const REPORT_TICKS: u32 = 5 * TICK_HZ;
+
+static SHARED_RTC: Mutex<RefCell<Option<RTC0>>> = Mutex::new(RefCell::new(None));
+static TICKED: Mutex<RefCell<bool>> = Mutex::new(RefCell::new(false));
+
+// Needs the low frequency clock, init_display starts it
+pub fn init_ticks(rtc: pac::RTC0) -> Ticks {
+    rtc.prescaler.write(|w| unsafe { w.prescaler().bits(0) });
+    rtc.evtenset.write(|w| w.compare0().set());
+    rtc.intenset.write(|w| w.compare0().set());
+    rtc.tasks_start.write(|w| unsafe { w.bits(1) });
+
+    cortex_m::interrupt::free(|cs| {
+        SHARED_RTC.borrow(cs).replace(Some(rtc));
+    });
+    unsafe { pac::NVIC::unmask(pac::interrupt::RTC0) };
+
+    let now = counter();
+    Ticks { idle_ticks: 0, report_from: now }
+}
+
+fn counter() -> u32 {
+    cortex_m::interrupt::free(|cs| {
+        SHARED_RTC.borrow(cs).borrow().as_ref().map_or(0, |rtc| rtc.counter.read().bits())
+    })
+}
+
+fn elapsed(from: u32, to: u32) -> u32 {
+    to.wrapping_sub(from) & COUNTER_MASK
+}
+
+// Sets the next compare event this many ticks from now
+fn schedule(ticks: u32) {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(rtc) = SHARED_RTC.borrow(cs).borrow().as_ref() {
+            let next = (rtc.counter.read().bits() + ticks.max(MIN_TICKS)) & COUNTER_MASK;
+            rtc.cc[0].write(|w| unsafe { w.compare().bits(next) });
+            TICKED.borrow(cs).replace(false);
+        }
+    })
+}
+
+// Owned by main, keeps count of how long the CPU slept
+pub struct Ticks {
+    idle_ticks: u32,
+    report_from: u32,
+}
+
+impl Ticks {
+    // Sleeps until the tick, other interrupts (display, buttons, speaker) wake it up on the way
+    pub fn sleep_ticks(&mut self, ticks: u32) {
+        schedule(ticks);
+        loop {
+            // wfi with interrupts masked still wakes up, the ISR then runs after free,
+            // so a tick can not slip in between the check and the sleep
+            let ticked = cortex_m::interrupt::free(|cs| {
+                if *TICKED.borrow(cs).borrow() {
+                    return true;
+                }
+                let before = counter();
+                asm::wfi();
+                self.idle_ticks += elapsed(before, counter());
+                false
+            });
+            if ticked {
+                break;
+            }
+        }
+        self.report();
+    }
+
+    fn report(&mut self) {
+        let now = counter();
+        let total = elapsed(self.report_from, now);
+        if total >= REPORT_TICKS {
+            rprintln!("idle {}%", self.idle_ticks as u64 * 100 / total as u64);
+            self.idle_ticks = 0;
+            self.report_from = now;
+        }
+    }
+}
+
+impl DelayNs for Ticks {
+    fn delay_ns(&mut self, ns: u32) {
+        self.sleep_ticks((ns as u64 * TICK_HZ as u64).div_ceil(1_000_000_000) as u32);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.sleep_ticks((ms as u64 * TICK_HZ as u64 / 1000) as u32);
+    }
+}
+
+#[interrupt]
+fn RTC0() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(rtc) = SHARED_RTC.borrow(cs).borrow().as_ref()
+            && rtc.events_compare[0].read().bits() != 0
+        {
+            rtc.events_compare[0].write(|w| unsafe { w.bits(0) });
+            TICKED.borrow(cs).replace(true);
+        }
+    });
+}
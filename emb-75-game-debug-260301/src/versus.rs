@@ -0,0 +1,139 @@
+use core::fmt::Write;
+use embedded_hal::delay::DelayNs;
+use led_matrix::scroll::ScrollingText;
+use microbit::{
+    board::Edge,
+    display::nonblocking::GreyscaleImage,
+    hal::{Rng, gpio::Level, uarte::{self, Baudrate, Parity, Uarte}},
+    pac,
+};
+use rtt_target::rprintln;
+use snake_core::{
+    app::Action,
+    versus::{MessageDecoder, Link, LinkError, Player, Versus, VersusStatus},
+};
+
+use crate::{SCROLL_STEP_MS, controls::Controls, display, serial_setup::UartePort};
+
+// Wait this long between looks at the line, LOST_AFTER of them in a row without a frame is a lost link
+const POLL_MS: u32 = 20;
+const VERSUS_STEP_MS: u32 = 400;
+// Keep answering after the end so the other board gets the last step too
+const LINGER_POLLS: u32 = 25;
+
+// The other board hangs off the edge connector: P0 to its P1, P1 to its P0 and GND to GND
+pub struct VersusLink {
+    port: UartePort<pac::UARTE1>,
+    decoder: MessageDecoder,
+    link: Link,
+}
+
+impl VersusLink {
+    pub fn new(uarte: pac::UARTE1, edge: Edge, nonce: u32) -> Self {
+        let pins = uarte::Pins {
+            txd: edge.e00.into_push_pull_output(Level::High).degrade(),
+            rxd: edge.e01.into_floating_input().degrade(),
+            cts: None,
+            rts: None,
+        };
+        let serial = Uarte::new(uarte, pins, Parity::EXCLUDED, Baudrate::BAUD115200);
+        let port = UartePort::new(
+            serial,
+            cortex_m::singleton!(: [u8; 1] = [0; 1]).unwrap(),
+            cortex_m::singleton!(: [u8; 1] = [0; 1]).unwrap(),
+        );
+        Self { port, decoder: MessageDecoder::new(), link: Link::new(nonce) }
+    }
+
+    // Forget the last game, the nonce picks seed and player again
+    fn restart(&mut self, nonce: u32) {
+        self.link = Link::new(nonce);
+        self.decoder = MessageDecoder::new();
+    }
+
+    // Sends what the link wants sent and takes in whatever came
+    fn poll(&mut self) -> Result<(), LinkError> {
+        if let Some(message) = self.link.outgoing() {
+            let _ = self.port.write_all(&message.encode());
+        }
+        let mut heard = false;
+        while let Ok(Some(byte)) = self.port.try_read() {
+            if let Some(message) = self.decoder.feed(byte) {
+                heard = true;
+                self.link.receive(message)?;
+            }
+        }
+        if heard { Ok(()) } else { self.link.poll_timeout() }
+    }
+
+    fn play(&mut self, controls: &mut Controls, timer: &mut impl DelayNs) -> Result<(VersusStatus, Player), LinkError> {
+        // No time limit on finding the other board, it may not be switched on yet
+        let (seed, me) = loop {
+            match self.poll() {
+                Ok(()) | Err(LinkError::Lost { .. }) => (),
+                Err(e) => return Err(e),
+            }
+            if let Some(start) = self.link.start() {
+                break start;
+            }
+            timer.delay_ms(POLL_MS);
+        };
+        rprintln!("Versus seed {:08x}, playing {:?}", seed, me);
+
+        let mut versus: Versus = Versus::new(seed);
+        while versus.status() == VersusStatus::Ongoing {
+            display::set_orientation(controls.update_orientation());
+            display::show_image(&GreyscaleImage::new(&versus.matrix(me)));
+            timer.delay_ms(VERSUS_STEP_MS);
+
+            let heading = versus.game(me).snake().direction;
+            let press = controls.next_press().map_or(Action::Nothing, |p| Action::Steer(p.turn()));
+            self.link.send_turn(controls.steer(heading, press), &versus);
+            let turns = loop {
+                self.poll()?;
+                if let Some(turns) = self.link.ready()? {
+                    break turns;
+                }
+                timer.delay_ms(POLL_MS);
+            };
+            versus.step(turns);
+            self.link.next_step();
+        }
+
+        display::show_image(&GreyscaleImage::new(&versus.matrix(me)));
+        for _ in 0..LINGER_POLLS {
+            let _ = self.poll();
+            timer.delay_ms(POLL_MS);
+        }
+        Ok((versus.status(), me))
+    }
+}
+
+// Never leaves versus mode, reset the board to play alone again
+pub fn run(uarte: pac::UARTE1, edge: Edge, controls: &mut Controls, rng: &mut Rng, timer: &mut impl DelayNs) -> ! {
+    let mut link = VersusLink::new(uarte, edge, rng.random_u32());
+    loop {
+        let result = link.play(controls, timer);
+        rprintln!("Versus: {:?}", result);
+
+        let mut text: ScrollingText<16> = ScrollingText::new("");
+        let _ = match result {
+            Ok((VersusStatus::Won(winner), me)) if winner == me => write!(text, "YOU WIN"),
+            Ok((VersusStatus::Won(_), _)) => write!(text, "YOU LOSE"),
+            Ok((_, _)) => write!(text, "DRAW"),
+            Err(LinkError::Desync { step }) => write!(text, "DESYNC {}", step),
+            Err(LinkError::Lost { step }) => write!(text, "LINK LOST {}", step),
+        };
+        // Round and round until a press starts the next game
+        let mut pressed = false;
+        while !pressed {
+            text.restart();
+            while !pressed && text.advance() {
+                display::show_image(&text);
+                timer.delay_ms(SCROLL_STEP_MS);
+                pressed = controls.next_press().is_some();
+            }
+        }
+        link.restart(rng.random_u32());
+    }
+}
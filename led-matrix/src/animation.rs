@@ -0,0 +1,165 @@
+
+// Keyframe animations: a list of frames, each shown for a number of ticks. What a tick is
+// (an RTC event, a timer, a loop iteration) is up to whoever calls Animator::tick.
+
+use tiny_led_matrix::Render;
+
+pub type Image = [[u8; 5]; 5];
+
+#[derive(Debug, Clone, Copy)]
+pub enum Frame {
+    Image(Image),
+    // Drawn again on every tick, gets the ticks since the frame came up
+    Procedural(fn(u32) -> Image),
+}
+
+impl Frame {
+    fn render(&self, tick: u32) -> Image {
+        match self {
+            Frame::Image(image) => *image,
+            Frame::Procedural(draw) => draw(tick),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Keyframe {
+    pub frame: Frame,
+    // At least one tick, 0 counts as 1
+    pub ticks: u32,
+}
+
+impl Keyframe {
+    pub const fn image(image: Image, ticks: u32) -> Self {
+        Self { frame: Frame::Image(image), ticks }
+    }
+
+    pub const fn procedural(draw: fn(u32) -> Image, ticks: u32) -> Self {
+        Self { frame: Frame::Procedural(draw), ticks }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Playback {
+    // Back to the first frame after the last one
+    Loop,
+    // Stops on the last frame
+    Once,
+    // Forwards, then backwards without showing the end frames twice, and so on
+    PingPong,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Animation {
+    pub frames: &'static [Keyframe],
+    pub playback: Playback,
+}
+
+impl Animation {
+    pub const fn new(frames: &'static [Keyframe], playback: Playback) -> Self {
+        Self { frames, playback }
+    }
+
+    // Ticks until a Once animation is finished, or one round of the others
+    pub fn len_ticks(&self) -> u32 {
+        let ticks = |k: &Keyframe| k.ticks.max(1);
+        let forward: u32 = self.frames.iter().map(ticks).sum();
+        match (self.playback, self.frames) {
+            (Playback::PingPong, [_, middle @ .., _]) => forward + middle.iter().map(ticks).sum::<u32>(),
+            _ => forward,
+        }
+    }
+}
+
+// Plays one animation at a time, the current image is kept so rendering is cheap
+pub struct Animator {
+    animation: Animation,
+    index: usize,
+    tick: u32,
+    backwards: bool,
+    finished: bool,
+    image: Image,
+}
+
+impl Animator {
+    pub fn new(animation: Animation) -> Self {
+        let mut animator = Self { animation, index: 0, tick: 0, backwards: false, finished: false, image: [[0; 5]; 5] };
+        animator.play(animation);
+        animator
+    }
+
+    // Starts the animation from its first frame, even if it is the one already playing
+    pub fn play(&mut self, animation: Animation) {
+        self.animation = animation;
+        self.index = 0;
+        self.tick = 0;
+        self.backwards = false;
+        self.finished = animation.frames.is_empty();
+        self.image = animation.frames.first().map_or([[0; 5]; 5], |k| k.frame.render(0));
+    }
+
+    // Goes by where the frames are, so keep animations in statics. A const may be copied to a
+    // new place on every use, or share its place with another one that has the same frames.
+    pub fn is_playing(&self, animation: &Animation) -> bool {
+        self.animation.playback == animation.playback && core::ptr::eq(self.animation.frames, animation.frames)
+    }
+
+    // Only a Once animation finishes, on its last frame
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn image(&self) -> Image {
+        self.image
+    }
+
+    // Moves on by one tick, true if the image may have changed and should be shown again
+    pub fn tick(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        let frames = self.animation.frames;
+        self.tick += 1;
+        if self.tick < frames[self.index].ticks.max(1) {
+            if let Frame::Procedural(draw) = frames[self.index].frame {
+                self.image = draw(self.tick);
+                return true;
+            }
+            return false;
+        }
+
+        let last = frames.len() - 1;
+        match self.animation.playback {
+            Playback::Loop => self.index = if self.index == last { 0 } else { self.index + 1 },
+            Playback::Once if self.index == last => {
+                self.finished = true;
+                return false;
+            },
+            Playback::Once => self.index += 1,
+            Playback::PingPong => {
+                if last == 0 {
+                    // Nowhere to go, start the one frame over
+                } else if self.backwards {
+                    self.index -= 1;
+                    self.backwards = self.index > 0;
+                } else {
+                    self.index += 1;
+                    self.backwards = self.index == last;
+                }
+            },
+        }
+        self.tick = 0;
+        self.image = frames[self.index].frame.render(0);
+        true
+    }
+}
+
+impl Render for Animator {
+    fn brightness_at(&self, x: usize, y: usize) -> u8 {
+        self.image[y][x]
+    }
+}
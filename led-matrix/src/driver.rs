@@ -0,0 +1,164 @@
+
+// Drives the matrix straight from 5 row and 5 column pins, like emb-10 does by hand, but one small
+// step per timer interrupt instead of blocking delays.
+//
+// Only one row is lit at a time. Each row stays up for `levels` ticks and a pixel of brightness b
+// stays on for the first b of them, so brightness is PWM on the column pins. Before the next row
+// comes up the old one is switched off, otherwise its pixels would ghost into the new row.
+
+use embedded_hal::digital::OutputPin;
+use tiny_led_matrix::Render;
+
+use crate::animation::Image;
+
+const ROWS: usize = 5;
+const COLS: usize = 5;
+
+// Which level turns an LED on through this pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Polarity {
+    ActiveHigh,
+    ActiveLow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    // Whole frames per second, below about 50 it starts to flicker
+    pub refresh_hz: u32,
+    // Brightness steps, images with 0..=9 are scaled to 0..=levels
+    pub levels: u8,
+    pub rows: Polarity,
+    pub cols: Polarity,
+}
+
+impl Config {
+    // micro:bit v2: the rows drive the anodes, the columns sink the current
+    pub const MICROBIT: Self = Self { refresh_hz: 100, levels: 9, rows: Polarity::ActiveHigh, cols: Polarity::ActiveLow };
+
+    // How often tick has to be called
+    pub fn tick_us(&self) -> u32 {
+        1_000_000 / (self.refresh_hz.max(1) * ROWS as u32 * self.levels.max(1) as u32)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::MICROBIT
+    }
+}
+
+pub struct MatrixDriver<R, C> {
+    rows: [R; ROWS],
+    cols: [C; COLS],
+    config: Config,
+    image: Image,
+    // The row and PWM step the next tick shows
+    row: usize,
+    step: u8,
+}
+
+impl<R, C> MatrixDriver<R, C>
+where
+    R: OutputPin,
+    C: OutputPin<Error = R::Error>,
+{
+    // Starts dark, with every pin off
+    pub fn new(rows: [R; ROWS], cols: [C; COLS], config: Config) -> Result<Self, R::Error> {
+        let config = Config { levels: config.levels.max(1), ..config };
+        let mut driver = Self { rows, cols, config, image: [[0; COLS]; ROWS], row: 0, step: 0 };
+        for row in 0..ROWS {
+            driver.set_row(row, false)?;
+        }
+        for col in 0..COLS {
+            driver.set_col(col, false)?;
+        }
+        Ok(driver)
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    // Taken over from the next tick on, anything brighter than 9 counts as 9
+    pub fn show(&mut self, image: &impl Render) {
+        self.show_image(core::array::from_fn(|y| core::array::from_fn(|x| image.brightness_at(x, y))));
+    }
+
+    pub fn show_image(&mut self, image: Image) {
+        let levels = self.config.levels as u16;
+        for (row, image_row) in self.image.iter_mut().zip(image) {
+            for (value, brightness) in row.iter_mut().zip(image_row) {
+                *value = match brightness.min(9) as u16 {
+                    0 => 0,
+                    // Dim pixels stay on, if only for one step
+                    brightness => ((brightness * levels + 4) / 9).max(1) as u8,
+                };
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.image = [[0; COLS]; ROWS];
+    }
+
+    // The brightness per pixel as it is scanned, 0..=levels
+    pub fn image(&self) -> Image {
+        self.image
+    }
+
+    // Call every Config::tick_us, e.g. from a periodic timer interrupt
+    pub fn tick(&mut self) -> Result<(), R::Error> {
+        let row = self.row;
+        if self.step == 0 {
+            self.set_row((row + ROWS - 1) % ROWS, false)?;
+            for col in 0..COLS {
+                self.set_col(col, self.image[row][col] > 0)?;
+            }
+            self.set_row(row, true)?;
+        } else {
+            for col in 0..COLS {
+                if self.image[row][col] == self.step {
+                    self.set_col(col, false)?;
+                }
+            }
+        }
+
+        self.step += 1;
+        if self.step >= self.config.levels {
+            self.step = 0;
+            self.row = (row + 1) % ROWS;
+        }
+        Ok(())
+    }
+
+    // Everything off, e.g. before going to sleep. The next tick starts lighting again.
+    pub fn blank(&mut self) -> Result<(), R::Error> {
+        for row in 0..ROWS {
+            self.set_row(row, false)?;
+        }
+        self.row = 0;
+        self.step = 0;
+        Ok(())
+    }
+
+    // Hands the pins back
+    pub fn free(self) -> ([R; ROWS], [C; COLS]) {
+        (self.rows, self.cols)
+    }
+
+    fn set_row(&mut self, row: usize, on: bool) -> Result<(), R::Error> {
+        set(&mut self.rows[row], self.config.rows, on)
+    }
+
+    fn set_col(&mut self, col: usize, on: bool) -> Result<(), R::Error> {
+        set(&mut self.cols[col], self.config.cols, on)
+    }
+}
+
+fn set<P: OutputPin>(pin: &mut P, polarity: Polarity, on: bool) -> Result<(), P::Error> {
+    if on == (polarity == Polarity::ActiveHigh) {
+        pin.set_high()
+    } else {
+        pin.set_low()
+    }
+}
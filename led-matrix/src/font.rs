@@ -0,0 +1,125 @@
+
+// A 5 pixel high font, glyphs are up to 5 columns wide and get one blank column between them
+
+pub const GLYPH_HEIGHT: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph {
+    width: u8,
+    // One byte per row, bit 4 is the leftmost column
+    rows: [u8; GLYPH_HEIGHT],
+}
+
+impl Glyph {
+    // Every row is drawn with '#' for lit and '.' for dark, all rows need the same width
+    pub const fn parse(art: [&str; GLYPH_HEIGHT]) -> Self {
+        let width = art[0].len();
+        assert!(width >= 1 && width <= 5, "glyph has to be 1 to 5 columns wide");
+        let mut rows = [0; GLYPH_HEIGHT];
+        let mut r = 0;
+        while r < GLYPH_HEIGHT {
+            let row = art[r].as_bytes();
+            assert!(row.len() == width, "glyph rows have different widths");
+            let mut c = 0;
+            while c < width {
+                match row[c] {
+                    b'#' => rows[r] |= 1 << (4 - c),
+                    b'.' => (),
+                    _ => panic!("unknown character in glyph, use '#' and '.'"),
+                }
+                c += 1;
+            }
+            r += 1;
+        }
+        Self { width: width as u8, rows }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width as usize
+    }
+
+    pub fn is_lit(&self, col: usize, row: usize) -> bool {
+        col < self.width() && row < GLYPH_HEIGHT && (self.rows[row] >> (4 - col)) & 1 == 1
+    }
+
+    // The lit rows of one column, bit 0 is the top row
+    pub fn column(&self, col: usize) -> u8 {
+        (0..GLYPH_HEIGHT).fold(0, |bits, row| bits | ((self.is_lit(col, row) as u8) << row))
+    }
+}
+
+const UNKNOWN: Glyph = Glyph::parse(["###.", "...#", ".##.", "....", ".#.."]);
+
+// Parse at compile time, a broken glyph is a build error
+macro_rules! glyph {
+    ($($row:literal),*) => {
+        const { Glyph::parse([$($row),*]) }
+    };
+}
+
+// Lower case letters are shown as upper case, anything without a glyph becomes '?'
+pub fn glyph(c: char) -> Glyph {
+    match c.to_ascii_uppercase() {
+        '0' => glyph!(".##.", "#..#", "#..#", "#..#", ".##."),
+        '1' => glyph!(".#.", "##.", ".#.", ".#.", "###"),
+        '2' => glyph!("###.", "...#", ".##.", "#...", "####"),
+        '3' => glyph!("###.", "...#", ".##.", "...#", "###."),
+        '4' => glyph!("#..#", "#..#", "####", "...#", "...#"),
+        '5' => glyph!("####", "#...", "###.", "...#", "###."),
+        '6' => glyph!(".##.", "#...", "###.", "#..#", ".##."),
+        '7' => glyph!("####", "...#", "..#.", ".#..", ".#.."),
+        '8' => glyph!(".##.", "#..#", ".##.", "#..#", ".##."),
+        '9' => glyph!(".##.", "#..#", ".###", "...#", ".##."),
+        'A' => glyph!(".##.", "#..#", "####", "#..#", "#..#"),
+        'B' => glyph!("###.", "#..#", "###.", "#..#", "###."),
+        'C' => glyph!(".###", "#...", "#...", "#...", ".###"),
+        'D' => glyph!("###.", "#..#", "#..#", "#..#", "###."),
+        'E' => glyph!("####", "#...", "###.", "#...", "####"),
+        'F' => glyph!("####", "#...", "###.", "#...", "#..."),
+        'G' => glyph!(".###", "#...", "#.##", "#..#", ".###"),
+        'H' => glyph!("#..#", "#..#", "####", "#..#", "#..#"),
+        'I' => glyph!("###", ".#.", ".#.", ".#.", "###"),
+        'J' => glyph!("...#", "...#", "...#", "#..#", ".##."),
+        'K' => glyph!("#..#", "#.#.", "##..", "#.#.", "#..#"),
+        'L' => glyph!("#...", "#...", "#...", "#...", "####"),
+        'M' => glyph!("#...#", "##.##", "#.#.#", "#...#", "#...#"),
+        'N' => glyph!("#...#", "##..#", "#.#.#", "#..##", "#...#"),
+        'O' => glyph!(".##.", "#..#", "#..#", "#..#", ".##."),
+        'P' => glyph!("###.", "#..#", "###.", "#...", "#..."),
+        'Q' => glyph!(".##.", "#..#", "#..#", "#.#.", ".#.#"),
+        'R' => glyph!("###.", "#..#", "###.", "#.#.", "#..#"),
+        'S' => glyph!(".###", "#...", ".##.", "...#", "###."),
+        'T' => glyph!("#####", "..#..", "..#..", "..#..", "..#.."),
+        'U' => glyph!("#..#", "#..#", "#..#", "#..#", ".##."),
+        'V' => glyph!("#...#", "#...#", "#...#", ".#.#.", "..#.."),
+        'W' => glyph!("#...#", "#...#", "#.#.#", "##.##", "#...#"),
+        'X' => glyph!("#...#", ".#.#.", "..#..", ".#.#.", "#...#"),
+        'Y' => glyph!("#...#", ".#.#.", "..#..", "..#..", "..#.."),
+        'Z' => glyph!("####", "...#", ".##.", "#...", "####"),
+        ' ' => glyph!("..", "..", "..", "..", ".."),
+        '.' => glyph!(".", ".", ".", ".", "#"),
+        ',' => glyph!(".", ".", ".", "#", "#"),
+        ':' => glyph!(".", "#", ".", "#", "."),
+        '!' => glyph!("#", "#", "#", ".", "#"),
+        '\'' => glyph!("#", "#", ".", ".", "."),
+        '-' => glyph!("...", "...", "###", "...", "..."),
+        '+' => glyph!("...", ".#.", "###", ".#.", "..."),
+        '=' => glyph!("...", "###", "...", "###", "..."),
+        '*' => glyph!("...", "#.#", ".#.", "#.#", "..."),
+        '/' => glyph!("....#", "...#.", "..#..", ".#...", "#...."),
+        '%' => glyph!("#...#", "...#.", "..#..", ".#...", "#...#"),
+        '(' => glyph!(".#", "#.", "#.", "#.", ".#"),
+        ')' => glyph!("#.", ".#", ".#", ".#", "#."),
+        '<' => glyph!("..#", ".#.", "#..", ".#.", "..#"),
+        '>' => glyph!("#..", ".#.", "..#", ".#.", "#.."),
+        '#' => glyph!(".#.#.", "#####", ".#.#.", "#####", ".#.#."),
+        '°' => glyph!(".#.", "#.#", ".#.", "...", "..."),
+        _ => UNKNOWN,
+    }
+}
+
+// Columns the text takes up, gaps included
+pub fn text_width(text: &str) -> usize {
+    let glyphs: usize = text.chars().map(|c| glyph(c).width()).sum();
+    glyphs + text.chars().count().saturating_sub(1)
+}
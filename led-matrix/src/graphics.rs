@@ -0,0 +1,154 @@
+
+// embedded-graphics on the matrix: draw lines, shapes and text into a Framebuffer, then show it.
+// The nonblocking Display takes the Framebuffer itself as Render, the blocking one takes image().
+// Colours are Gray4, 0..=15 is scaled down to the 0..=9 the display knows.
+
+use core::convert::Infallible;
+
+use embedded_graphics::{
+    Pixel,
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    pixelcolor::{Gray4, GrayColor},
+    primitives::Rectangle,
+    text::{
+        Baseline,
+        renderer::{CharacterStyle, TextMetrics, TextRenderer},
+    },
+};
+use tiny_led_matrix::Render;
+
+use crate::{
+    animation::Image,
+    font::{self, GLYPH_HEIGHT},
+};
+
+const SIZE: usize = 5;
+
+// Rounded, so full white is 9 and anything not black stays lit
+pub fn brightness(color: Gray4) -> u8 {
+    match color.luma() {
+        0 => 0,
+        luma => ((luma as u16 * 9 + 7) / 15).max(1) as u8,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Framebuffer {
+    pixels: Image,
+}
+
+impl Framebuffer {
+    pub const fn new() -> Self {
+        Self { pixels: [[0; SIZE]; SIZE] }
+    }
+
+    pub fn image(&self) -> Image {
+        self.pixels
+    }
+}
+
+impl OriginDimensions for Framebuffer {
+    fn size(&self) -> Size {
+        Size::new(SIZE as u32, SIZE as u32)
+    }
+}
+
+impl DrawTarget for Framebuffer {
+    type Color = Gray4;
+    type Error = Infallible;
+
+    // Whatever falls off the edge is dropped
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if (0..SIZE as i32).contains(&point.x) && (0..SIZE as i32).contains(&point.y) {
+                self.pixels[point.y as usize][point.x as usize] = brightness(color);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Render for Framebuffer {
+    fn brightness_at(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y][x]
+    }
+}
+
+// The 5 pixel font from font.rs as a text style, e.g.
+// Text::with_baseline("7", Point::new(1, 0), GlyphStyle::new(Gray4::WHITE), Baseline::Top)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphStyle {
+    pub color: Gray4,
+}
+
+impl GlyphStyle {
+    pub const fn new(color: Gray4) -> Self {
+        Self { color }
+    }
+
+    fn top(position: Point, baseline: Baseline) -> i32 {
+        match baseline {
+            Baseline::Top => position.y,
+            Baseline::Middle => position.y - GLYPH_HEIGHT as i32 / 2,
+            Baseline::Bottom | Baseline::Alphabetic => position.y - (GLYPH_HEIGHT as i32 - 1),
+        }
+    }
+
+    // Glyphs plus the blank column after each one, so the next text can follow on directly
+    fn advance(text: &str) -> i32 {
+        text.chars().map(|c| font::glyph(c).width() as i32 + 1).sum()
+    }
+}
+
+impl CharacterStyle for GlyphStyle {
+    type Color = Gray4;
+
+    fn set_text_color(&mut self, text_color: Option<Self::Color>) {
+        self.color = text_color.unwrap_or(Gray4::BLACK);
+    }
+}
+
+impl TextRenderer for GlyphStyle {
+    type Color = Gray4;
+
+    fn draw_string<D>(&self, text: &str, position: Point, baseline: Baseline, target: &mut D) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let top = Self::top(position, baseline);
+        let mut x = position.x;
+        for c in text.chars() {
+            let glyph = font::glyph(c);
+            let lit = (0..glyph.width())
+                .flat_map(|col| (0..GLYPH_HEIGHT).map(move |row| (col, row)))
+                .filter(|(col, row)| glyph.is_lit(*col, *row))
+                .map(|(col, row)| Pixel(Point::new(x + col as i32, top + row as i32), self.color));
+            target.draw_iter(lit)?;
+            x += glyph.width() as i32 + 1;
+        }
+        Ok(Point::new(x, position.y))
+    }
+
+    fn draw_whitespace<D>(&self, width: u32, position: Point, _baseline: Baseline, _target: &mut D) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        Ok(position + Point::new(width as i32, 0))
+    }
+
+    fn measure_string(&self, text: &str, position: Point, baseline: Baseline) -> TextMetrics {
+        let top = Point::new(position.x, Self::top(position, baseline));
+        TextMetrics {
+            bounding_box: Rectangle::new(top, Size::new(font::text_width(text) as u32, GLYPH_HEIGHT as u32)),
+            next_position: position + Point::new(Self::advance(text), 0),
+        }
+    }
+
+    fn line_height(&self) -> u32 {
+        GLYPH_HEIGHT as u32 + 1
+    }
+}
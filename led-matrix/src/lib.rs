@@ -0,0 +1,16 @@
+#![no_std]
+
+// Things to show on the 5x5 LED matrix, free of any HAL so they can be tested on the host.
+// Images are [[u8; 5]; 5] with brightness 0..=9, the same as GreyscaleImage takes.
+
+pub mod animation;
+pub mod driver;
+pub mod font;
+pub mod graphics;
+pub mod mirror;
+pub mod orientation;
+pub mod scroll;
+pub mod sprite;
+pub mod sprites;
+pub mod transition;
+pub mod widgets;
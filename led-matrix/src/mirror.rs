@@ -0,0 +1,68 @@
+
+// What the display shows, as frames over a serial line so a host can draw along.
+// Frame layout: SYNC | sequence | 13 bytes pixels | crc8
+// Pixels go row by row, two per byte with the first in the high nibble, the last low nibble is 0.
+// Brightness only goes up to 9, so 0xa5 never turns up in the pixel bytes. It can still be the
+// sequence or the crc, the frame decoder looks for the next sync byte when a frame is broken.
+
+use serial_frame::FrameDecoder;
+
+use crate::animation::Image;
+
+const SIZE: usize = 5;
+const PIXEL_BYTES: usize = (SIZE * SIZE).div_ceil(2);
+pub const FRAME_LEN: usize = PIXEL_BYTES + 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MirrorFrame {
+    // Counts up per frame sent and wraps, a gap means frames got lost on the way
+    pub sequence: u8,
+    pub image: Image,
+}
+
+impl MirrorFrame {
+    pub fn new(sequence: u8, image: Image) -> Self {
+        Self { sequence, image }
+    }
+
+    // Anything brighter than 9 is sent as 9
+    pub fn encode(&self) -> [u8; FRAME_LEN] {
+        let mut frame = [0; FRAME_LEN];
+        frame[1] = self.sequence;
+        for (i, brightness) in self.image.iter().flatten().enumerate() {
+            let shift = if i % 2 == 0 { 4 } else { 0 };
+            frame[2 + i / 2] |= (*brightness).min(9) << shift;
+        }
+        serial_frame::seal(&mut frame);
+        frame
+    }
+
+    // Sync byte and crc are checked by the decoder already
+    fn decode(frame: &[u8; FRAME_LEN]) -> Option<Self> {
+        let mut image = [[0; SIZE]; SIZE];
+        for (i, pixel) in image.iter_mut().flatten().enumerate() {
+            let shift = if i % 2 == 0 { 4 } else { 0 };
+            *pixel = (frame[2 + i / 2] >> shift) & 0x0f;
+            if *pixel > 9 {
+                return None;
+            }
+        }
+        Some(Self { sequence: frame[1], image })
+    }
+}
+
+// Collects bytes from the line into frames, garbage and broken frames are skipped
+#[derive(Debug, Default)]
+pub struct MirrorDecoder {
+    frames: FrameDecoder<FRAME_LEN>,
+}
+
+impl MirrorDecoder {
+    pub const fn new() -> Self {
+        Self { frames: FrameDecoder::new() }
+    }
+
+    pub fn feed(&mut self, byte: u8) -> Option<MirrorFrame> {
+        MirrorFrame::decode(&self.frames.feed(byte)?)
+    }
+}
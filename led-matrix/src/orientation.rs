@@ -0,0 +1,161 @@
+
+// Turning images to match how the board is held. Everything is drawn as if the USB connector is
+// at the top, Orientation turns that around before it reaches the display and OrientationTracker
+// picks the orientation from the accelerometer.
+//
+// Coordinates are x to the right and y down, like the rows and columns of an Image.
+
+use tiny_led_matrix::Render;
+
+use crate::animation::Image;
+
+const LAST: i32 = 4;
+
+// Clockwise quarter turns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Rotation {
+    pub fn inverse(self) -> Self {
+        match self {
+            Rotation::Deg0 => Rotation::Deg0,
+            Rotation::Deg90 => Rotation::Deg270,
+            Rotation::Deg180 => Rotation::Deg180,
+            Rotation::Deg270 => Rotation::Deg90,
+        }
+    }
+}
+
+// Mirrored left to right first, then rotated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Orientation {
+    pub rotation: Rotation,
+    pub mirrored: bool,
+}
+
+impl Orientation {
+    pub const UPRIGHT: Self = Self { rotation: Rotation::Deg0, mirrored: false };
+
+    pub const fn new(rotation: Rotation, mirrored: bool) -> Self {
+        Self { rotation, mirrored }
+    }
+
+    // Undoes this one, a mirror turns the rotation around so it stays the same
+    pub fn inverse(self) -> Self {
+        let rotation = if self.mirrored { self.rotation } else { self.rotation.inverse() };
+        Self { rotation, mirrored: self.mirrored }
+    }
+
+    // Where a step of (dx, dy) on the image ends up on the display
+    pub fn map_offset(self, dx: i32, dy: i32) -> (i32, i32) {
+        let dx = if self.mirrored { -dx } else { dx };
+        match self.rotation {
+            Rotation::Deg0 => (dx, dy),
+            Rotation::Deg90 => (-dy, dx),
+            Rotation::Deg180 => (-dx, -dy),
+            Rotation::Deg270 => (dy, -dx),
+        }
+    }
+
+    // Where pixel (x, y) of the image ends up on the display
+    pub fn map_point(self, x: usize, y: usize) -> (usize, usize) {
+        // Turned around the centre pixel
+        let (dx, dy) = self.map_offset(x as i32 - LAST / 2, y as i32 - LAST / 2);
+        ((dx + LAST / 2) as usize, (dy + LAST / 2) as usize)
+    }
+
+    pub fn apply(self, image: &Image) -> Image {
+        let mut turned = [[0; 5]; 5];
+        for (y, row) in image.iter().enumerate() {
+            for (x, brightness) in row.iter().enumerate() {
+                let (tx, ty) = self.map_point(x, y);
+                turned[ty][tx] = *brightness;
+            }
+        }
+        turned
+    }
+
+    // Any Render turned without copying it, e.g. a GreyscaleImage or ScrollingText
+    pub fn render<R: Render>(self, image: &R) -> Oriented<'_, R> {
+        Oriented { image, inverse: self.inverse() }
+    }
+}
+
+pub struct Oriented<'a, R> {
+    image: &'a R,
+    inverse: Orientation,
+}
+
+impl<R: Render> Render for Oriented<'_, R> {
+    fn brightness_at(&self, x: usize, y: usize) -> u8 {
+        let (x, y) = self.inverse.map_point(x, y);
+        self.image.brightness_at(x, y)
+    }
+}
+
+// Picks the orientation from gravity in milli-g: x to the right, y towards the bottom edge and
+// z out of the back, so lying face down gives about +1000 on z. Mirrored while face down.
+//
+// A new orientation has to win `settle` readings in a row before it is taken. On top of that the
+// edge pointing down only changes when one axis clearly beats the other, so holding the board
+// near 45 degrees or lying flat keeps what there was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrientationTracker {
+    threshold_mg: i32,
+    settle: u8,
+    current: Orientation,
+    candidate: Orientation,
+    seen: u8,
+}
+
+impl OrientationTracker {
+    pub const fn new(threshold_mg: i32, settle: u8) -> Self {
+        Self { threshold_mg, settle, current: Orientation::UPRIGHT, candidate: Orientation::UPRIGHT, seen: 0 }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.current
+    }
+
+    pub fn update(&mut self, x_mg: i32, y_mg: i32, z_mg: i32) -> Orientation {
+        let along_x = x_mg.abs() >= y_mg.abs();
+        let (major, minor) = if along_x { (x_mg, y_mg) } else { (y_mg, x_mg) };
+        let clear = major.abs() >= self.threshold_mg && major.abs() - minor.abs() >= self.threshold_mg / 2;
+        // Turned so the bottom of the image is on the edge pointing down
+        let rotation = match (clear, along_x, major > 0) {
+            (false, ..) => self.current.rotation,
+            (true, false, true) => Rotation::Deg0,
+            (true, false, false) => Rotation::Deg180,
+            (true, true, true) => Rotation::Deg270,
+            (true, true, false) => Rotation::Deg90,
+        };
+        let mirrored = if z_mg >= self.threshold_mg {
+            true
+        } else if z_mg <= -self.threshold_mg {
+            false
+        } else {
+            self.current.mirrored
+        };
+
+        let seen = Orientation::new(rotation, mirrored);
+        if seen == self.current {
+            self.seen = 0;
+        } else if seen == self.candidate {
+            self.seen = self.seen.saturating_add(1);
+        } else {
+            self.candidate = seen;
+            self.seen = 1;
+        }
+        if self.seen >= self.settle {
+            self.current = seen;
+            self.seen = 0;
+        }
+        self.current
+    }
+}
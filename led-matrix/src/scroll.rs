@@ -0,0 +1,118 @@
+
+use core::fmt;
+use heapless::String;
+use tiny_led_matrix::Render;
+
+use crate::font::{self, GLYPH_HEIGHT};
+
+const SCREEN: usize = 5;
+
+// Text moving from right to left across the matrix, one column per advance.
+// It starts on a blank screen and is finished once the last column has left on the left side.
+pub struct ScrollingText<const N: usize> {
+    text: String<N>,
+    offset: usize,
+    brightness: u8,
+}
+
+impl<const N: usize> ScrollingText<N> {
+    pub fn new(text: &str) -> Self {
+        let mut scrolling = Self { text: String::new(), offset: 0, brightness: 9 };
+        scrolling.set_text(text);
+        scrolling
+    }
+
+    // Starts scrolling from the beginning, whatever does not fit into N bytes is cut off
+    pub fn set_text(&mut self, text: &str) {
+        self.text.clear();
+        for c in text.chars() {
+            if self.text.push(c).is_err() {
+                break;
+            }
+        }
+        self.offset = 0;
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness.min(9);
+    }
+
+    // Number of advances until the screen is blank again
+    pub fn len(&self) -> usize {
+        font::text_width(&self.text) + SCREEN
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.offset >= self.len()
+    }
+
+    // Moves one column, returns false once the text is gone and nothing moved
+    pub fn advance(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.offset += 1;
+        true
+    }
+
+    pub fn restart(&mut self) {
+        self.offset = 0;
+    }
+
+    // Lit rows of one column of the whole strip: the screen width of blank, then the text
+    fn column(&self, strip_col: usize) -> u8 {
+        let Some(mut col) = strip_col.checked_sub(SCREEN) else {
+            return 0;
+        };
+        for c in self.text.chars() {
+            let glyph = font::glyph(c);
+            if col < glyph.width() {
+                return glyph.column(col);
+            }
+            // Skip the glyph and the blank column after it
+            col = match col.checked_sub(glyph.width() + 1) {
+                Some(col) => col,
+                None => return 0,
+            };
+        }
+        0
+    }
+
+    pub fn frame(&self) -> [[u8; 5]; 5] {
+        let mut values = [[0; 5]; 5];
+        for x in 0..SCREEN {
+            let column = self.column(self.offset + x);
+            for (y, row) in values.iter_mut().enumerate().take(GLYPH_HEIGHT) {
+                if (column >> y) & 1 == 1 {
+                    row[x] = self.brightness;
+                }
+            }
+        }
+        values
+    }
+}
+
+// Lets the text be built with write!, e.g. write!(text, "SCORE {}", score)
+impl<const N: usize> fmt::Write for ScrollingText<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.text.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> Render for ScrollingText<N> {
+    fn brightness_at(&self, x: usize, y: usize) -> u8 {
+        if (self.column(self.offset + x) >> y) & 1 == 1 {
+            self.brightness
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,80 @@
+
+// Images drawn as ASCII art, one line per row: '.' is dark, '#' is full brightness and a digit
+// is that brightness. Spaces and blank lines are ignored so the art can be indented.
+//
+//  const ARROW: Image = sprite!("
+//      ..#..
+//      .#9#.
+//      #.6.#
+//      ..3..
+//      ..1..
+//  ");
+
+use crate::animation::Image;
+
+const SIZE: usize = 5;
+
+// Use it through sprite! or in a const, then a typo in the art is a compile error
+pub const fn parse(art: &str) -> Image {
+    let bytes = art.as_bytes();
+    let mut image = [[0; SIZE]; SIZE];
+    let mut row = 0;
+    let mut col = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\n' => {
+                if col != 0 {
+                    assert!(col == SIZE, "sprite row is not 5 wide");
+                    row += 1;
+                    col = 0;
+                }
+            },
+            b' ' | b'\t' | b'\r' => (),
+            c @ (b'.' | b'#' | b'0'..=b'9') => {
+                assert!(row < SIZE, "sprite has more than 5 rows");
+                assert!(col < SIZE, "sprite row is not 5 wide");
+                image[row][col] = match c {
+                    b'.' => 0,
+                    b'#' => 9,
+                    digit => digit - b'0',
+                };
+                col += 1;
+            },
+            _ => panic!("unknown character in sprite, use '.', '#' or a brightness 0 to 9"),
+        }
+        i += 1;
+    }
+    if col != 0 {
+        assert!(col == SIZE, "sprite row is not 5 wide");
+        row += 1;
+    }
+    assert!(row == SIZE, "sprite does not have 5 rows");
+    image
+}
+
+/// Always parsed at compile time, also outside of consts
+///
+/// A row that is not 5 wide does not compile:
+///
+/// ```compile_fail
+/// let _ = led_matrix::sprite!("#####\n####\n#####\n#####\n#####");
+/// ```
+///
+/// Neither does a character other than '.', '#' or a digit:
+///
+/// ```compile_fail
+/// let _ = led_matrix::sprite!("#####\n##x##\n#####\n#####\n#####");
+/// ```
+///
+/// Both lines fixed it goes through:
+///
+/// ```
+/// let _ = led_matrix::sprite!("#####\n##.##\n#####\n#####\n#####");
+/// ```
+#[macro_export]
+macro_rules! sprite {
+    ($art:expr) => {
+        const { $crate::sprite::parse($art) }
+    };
+}
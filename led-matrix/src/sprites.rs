@@ -0,0 +1,291 @@
+
+// Ready made images, written with the sprite! macro so they are checked when the crate builds.
+// Lists of directions go clockwise and start at north, the top edge of the board.
+
+use crate::{animation::Image, sprite};
+
+pub const ARROW_N: Image = sprite!("
+    ..#..
+    .###.
+    #.#.#
+    ..#..
+    ..#..
+");
+
+pub const ARROW_NE: Image = sprite!("
+    ..###
+    ...##
+    ..#.#
+    .#...
+    #....
+");
+
+pub const ARROW_E: Image = sprite!("
+    ..#..
+    ...#.
+    #####
+    ...#.
+    ..#..
+");
+
+pub const ARROW_SE: Image = sprite!("
+    #....
+    .#...
+    ..#.#
+    ...##
+    ..###
+");
+
+pub const ARROW_S: Image = sprite!("
+    ..#..
+    ..#..
+    #.#.#
+    .###.
+    ..#..
+");
+
+pub const ARROW_SW: Image = sprite!("
+    ....#
+    ...#.
+    #.#..
+    ##...
+    ###..
+");
+
+pub const ARROW_W: Image = sprite!("
+    ..#..
+    .#...
+    #####
+    .#...
+    ..#..
+");
+
+pub const ARROW_NW: Image = sprite!("
+    ###..
+    ##...
+    #.#..
+    ...#.
+    ....#
+");
+
+pub const ARROWS: [Image; 8] = [ARROW_N, ARROW_NE, ARROW_E, ARROW_SE, ARROW_S, ARROW_SW, ARROW_W, ARROW_NW];
+
+pub const HEART: Image = sprite!("
+    .#.#.
+    #####
+    #####
+    .###.
+    ..#..
+");
+
+pub const SMALL_HEART: Image = sprite!("
+    .....
+    .#.#.
+    .###.
+    ..#..
+    .....
+");
+
+// A tick
+pub const YES: Image = sprite!("
+    .....
+    ....#
+    ...#.
+    #.#..
+    .#...
+");
+
+pub const NO: Image = sprite!("
+    #...#
+    .#.#.
+    ..#..
+    .#.#.
+    #...#
+");
+
+pub const HAPPY: Image = sprite!("
+    .....
+    .#.#.
+    .....
+    #...#
+    .###.
+");
+
+pub const SAD: Image = sprite!("
+    .....
+    .#.#.
+    .....
+    .###.
+    #...#
+");
+
+pub const SKULL: Image = sprite!("
+    .###.
+    #.#.#
+    #####
+    .###.
+    .###.
+");
+
+pub const DIAMOND: Image = sprite!("
+    ..#..
+    .#.#.
+    #...#
+    .#.#.
+    ..#..
+");
+
+pub const SQUARE: Image = sprite!("
+    #####
+    #...#
+    #...#
+    #...#
+    #####
+");
+
+pub const DOT: Image = sprite!("
+    .....
+    .....
+    ..#..
+    .....
+    .....
+");
+
+// Compass needles: the tip on the edge, a dimmer shaft and the hub in the middle.
+// The in-between directions split the shaft over the two cells it passes.
+pub const NEEDLE_N: Image = sprite!("
+    ..9..
+    ..6..
+    ..4..
+    .....
+    .....
+");
+
+pub const NEEDLE_NNE: Image = sprite!("
+    ...9.
+    ..33.
+    ..4..
+    .....
+    .....
+");
+
+pub const NEEDLE_NE: Image = sprite!("
+    ....9
+    ...6.
+    ..4..
+    .....
+    .....
+");
+
+pub const NEEDLE_ENE: Image = sprite!("
+    .....
+    ...39
+    ..43.
+    .....
+    .....
+");
+
+pub const NEEDLE_E: Image = sprite!("
+    .....
+    .....
+    ..469
+    .....
+    .....
+");
+
+pub const NEEDLE_ESE: Image = sprite!("
+    .....
+    .....
+    ..43.
+    ...39
+    .....
+");
+
+pub const NEEDLE_SE: Image = sprite!("
+    .....
+    .....
+    ..4..
+    ...6.
+    ....9
+");
+
+pub const NEEDLE_SSE: Image = sprite!("
+    .....
+    .....
+    ..4..
+    ..33.
+    ...9.
+");
+
+pub const NEEDLE_S: Image = sprite!("
+    .....
+    .....
+    ..4..
+    ..6..
+    ..9..
+");
+
+pub const NEEDLE_SSW: Image = sprite!("
+    .....
+    .....
+    ..4..
+    .33..
+    .9...
+");
+
+pub const NEEDLE_SW: Image = sprite!("
+    .....
+    .....
+    ..4..
+    .6...
+    9....
+");
+
+pub const NEEDLE_WSW: Image = sprite!("
+    .....
+    .....
+    .34..
+    93...
+    .....
+");
+
+pub const NEEDLE_W: Image = sprite!("
+    .....
+    .....
+    964..
+    .....
+    .....
+");
+
+pub const NEEDLE_WNW: Image = sprite!("
+    .....
+    93...
+    .34..
+    .....
+    .....
+");
+
+pub const NEEDLE_NW: Image = sprite!("
+    9....
+    .6...
+    ..4..
+    .....
+    .....
+");
+
+pub const NEEDLE_NNW: Image = sprite!("
+    .9...
+    .33..
+    ..4..
+    .....
+    .....
+");
+
+pub const COMPASS_8: [Image; 8] = [
+    NEEDLE_N, NEEDLE_NE, NEEDLE_E, NEEDLE_SE, NEEDLE_S, NEEDLE_SW, NEEDLE_W, NEEDLE_NW,
+];
+
+pub const COMPASS_16: [Image; 16] = [
+    NEEDLE_N, NEEDLE_NNE, NEEDLE_NE, NEEDLE_ENE, NEEDLE_E, NEEDLE_ESE, NEEDLE_SE, NEEDLE_SSE,
+    NEEDLE_S, NEEDLE_SSW, NEEDLE_SW, NEEDLE_WSW, NEEDLE_W, NEEDLE_WNW, NEEDLE_NW, NEEDLE_NNW,
+];
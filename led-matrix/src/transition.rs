@@ -0,0 +1,135 @@
+
+// Going from one image to another over a number of frames instead of all at once.
+// Only the frame math lives here, whoever shows the frames decides how long each one stays up,
+// e.g. one per timer tick.
+
+use tiny_led_matrix::Render;
+
+use crate::animation::Image;
+
+const SIZE: usize = 5;
+const CELLS: usize = SIZE * SIZE;
+
+// The way the new image moves in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    // Every pixel blends from the old brightness to the new one
+    Crossfade,
+    // An edge sweeps across, the new image is already in place behind it
+    Wipe(Direction),
+    // The new image pushes the old one off the screen
+    Slide(Direction),
+    // Pixels switch over one by one in an order picked by the seed
+    Dissolve { seed: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transition {
+    from: Image,
+    to: Image,
+    effect: Effect,
+    frames: u32,
+    frame: u32,
+    // For Dissolve: the frame share at which each cell switches, 0..25
+    order: [u8; CELLS],
+}
+
+impl Transition {
+    // Frame 0 is the old image, frame `frames` the new one, at least 1
+    pub fn new(from: Image, to: Image, effect: Effect, frames: u32) -> Self {
+        let order = match effect {
+            Effect::Dissolve { seed } => shuffled(seed),
+            _ => core::array::from_fn(|i| i as u8),
+        };
+        Self { from, to, effect, frames: frames.max(1), frame: 0, order }
+    }
+
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.frames
+    }
+
+    // The frame last handed out by next_frame, the old image before the first call
+    pub fn current(&self) -> Image {
+        self.frame_at(self.frame)
+    }
+
+    // Frames 1 to `frames`, then None. Frame 0 is what is on the screen already.
+    pub fn next_frame(&mut self) -> Option<Image> {
+        if self.is_finished() {
+            return None;
+        }
+        self.frame += 1;
+        Some(self.current())
+    }
+
+    pub fn frame_at(&self, frame: u32) -> Image {
+        let frame = frame.min(self.frames);
+        let n = self.frames;
+        // How many columns or rows the edge has moved on
+        let edge = (SIZE as u32 * frame / n) as usize;
+        let (from, to) = (&self.from, &self.to);
+        core::array::from_fn(|r| {
+            core::array::from_fn(|c| match self.effect {
+                Effect::Crossfade => {
+                    let blend = from[r][c] as u32 * (n - frame) + to[r][c] as u32 * frame;
+                    ((blend + n / 2) / n) as u8
+                },
+                Effect::Wipe(direction) => {
+                    let revealed = match direction {
+                        Direction::Right => c < edge,
+                        Direction::Left => c >= SIZE - edge,
+                        Direction::Down => r < edge,
+                        Direction::Up => r >= SIZE - edge,
+                    };
+                    if revealed { to[r][c] } else { from[r][c] }
+                },
+                Effect::Slide(direction) => match direction {
+                    Direction::Right if c < edge => to[r][c + SIZE - edge],
+                    Direction::Right => from[r][c - edge],
+                    Direction::Left if c >= SIZE - edge => to[r][c + edge - SIZE],
+                    Direction::Left => from[r][c + edge],
+                    Direction::Down if r < edge => to[r + SIZE - edge][c],
+                    Direction::Down => from[r - edge][c],
+                    Direction::Up if r >= SIZE - edge => to[r + edge - SIZE][c],
+                    Direction::Up => from[r + edge][c],
+                },
+                Effect::Dissolve { .. } => {
+                    let switched = (CELLS as u32 * frame / n) as usize;
+                    if (self.order[r * SIZE + c] as usize) < switched { to[r][c] } else { from[r][c] }
+                },
+            })
+        })
+    }
+}
+
+impl Render for Transition {
+    fn brightness_at(&self, x: usize, y: usize) -> u8 {
+        self.current()[y][x]
+    }
+}
+
+// A permutation of 0..25 from a xorshift, so the same seed dissolves the same way every time.
+// The same xorshift as the game's Prng, written out here so the display library needs no game.
+fn shuffled(seed: u32) -> [u8; CELLS] {
+    let mut order: [u8; CELLS] = core::array::from_fn(|i| i as u8);
+    let mut x = if seed == 0 { 1 } else { seed };
+    for i in (1..CELLS).rev() {
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        order.swap(i, x as usize % (i + 1));
+    }
+    order
+}
@@ -0,0 +1,184 @@
+
+// Ways to show a number on the matrix, for watching a sensor without RTT. Every widget takes the
+// range the input is expected in, anything outside is clamped. Where a bar ends between two
+// pixels the last one is lit partly, so a slow change shows as a fade instead of a jump.
+
+use crate::animation::Image;
+
+const SIZE: usize = 5;
+const FULL: u32 = 9;
+// Marks zero on the centre meter
+const ZERO_MARK: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl Range {
+    pub const fn new(min: i32, max: i32) -> Self {
+        Self { min, max }
+    }
+
+    // min gives 0, max gives steps, rounded to the nearest step
+    pub fn scale(&self, value: i32, steps: u32) -> u32 {
+        let (min, max) = (self.min as i64, self.max as i64);
+        if max <= min {
+            return 0;
+        }
+        let value = (value as i64).clamp(min, max) - min;
+        ((value * steps as i64 + (max - min) / 2) / (max - min)) as u32
+    }
+}
+
+// `level` out of pixels * 9, filled pixel by pixel with the last one partly lit
+fn fill(level: u32, pixels: usize) -> impl Iterator<Item = u8> {
+    (0..pixels as u32).map(move |i| level.saturating_sub(i * FULL).min(FULL) as u8)
+}
+
+// One column per value, growing up from the bottom. Up to 5 values, from the left.
+pub fn vertical_bars(values: &[i32], range: Range) -> Image {
+    let mut image = [[0; SIZE]; SIZE];
+    for (col, value) in values.iter().take(SIZE).enumerate() {
+        for (i, brightness) in fill(range.scale(*value, SIZE as u32 * FULL), SIZE).enumerate() {
+            image[SIZE - 1 - i][col] = brightness;
+        }
+    }
+    image
+}
+
+// One row per value, growing to the right. Up to 5 values, from the top.
+pub fn horizontal_bars(values: &[i32], range: Range) -> Image {
+    let mut image = [[0; SIZE]; SIZE];
+    for (row, value) in values.iter().take(SIZE).enumerate() {
+        for (col, brightness) in fill(range.scale(*value, SIZE as u32 * FULL), SIZE).enumerate() {
+            image[row][col] = brightness;
+        }
+    }
+    image
+}
+
+// For signed values: the dim middle column is zero, positive values fill to the right and
+// negative ones to the left, each side scaled to its end of the range
+pub fn centre_meter(value: i32, range: Range) -> Image {
+    let half = SIZE / 2;
+    let mut row = [0; SIZE];
+    row[half] = ZERO_MARK;
+    if value > 0 && range.max > 0 {
+        let level = Range::new(0, range.max).scale(value, half as u32 * FULL);
+        for (i, brightness) in fill(level, half).enumerate() {
+            row[half + 1 + i] = brightness;
+        }
+    } else if value < 0 && range.min < 0 {
+        let level = Range::new(0, -range.min).scale(-value, half as u32 * FULL);
+        for (i, brightness) in fill(level, half).enumerate() {
+            row[half - 1 - i] = brightness;
+        }
+    }
+    [row; SIZE]
+}
+
+// A vertical bar that leaves its highest point lit for a while, like the peak on an audio meter.
+// The peak stays for `hold` updates and then sinks by `decay` (in ninths of a pixel) per update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeakMeter {
+    range: Range,
+    hold: u32,
+    decay: u32,
+    level: u32,
+    peak: u32,
+    held: u32,
+}
+
+impl PeakMeter {
+    pub const fn new(range: Range, hold: u32, decay: u32) -> Self {
+        Self { range, hold, decay, level: 0, peak: 0, held: 0 }
+    }
+
+    pub fn update(&mut self, value: i32) -> Image {
+        self.level = self.range.scale(value, SIZE as u32 * FULL);
+        if self.level >= self.peak {
+            self.peak = self.level;
+            self.held = 0;
+        } else if self.held < self.hold {
+            self.held += 1;
+        } else {
+            self.peak = self.peak.saturating_sub(self.decay).max(self.level);
+        }
+        self.image()
+    }
+
+    // In ninths of a pixel, 0..=45
+    pub fn peak(&self) -> u32 {
+        self.peak
+    }
+
+    pub fn image(&self) -> Image {
+        let mut image = [[0; SIZE]; SIZE];
+        for (i, brightness) in fill(self.level, SIZE).enumerate() {
+            image[SIZE - 1 - i] = [brightness; SIZE];
+        }
+        // The row the peak reached, lit fully above the bar
+        if self.peak > 0 {
+            let row = (self.peak - 1) / FULL;
+            image[SIZE - 1 - row as usize] = [FULL as u8; SIZE];
+        }
+        image
+    }
+}
+
+// The last 5 values as dots, the newest on the right. A value between two rows splits its
+// brightness over both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sparkline {
+    range: Range,
+    samples: [Option<i32>; SIZE],
+}
+
+impl Sparkline {
+    pub const fn new(range: Range) -> Self {
+        Self { range, samples: [None; SIZE] }
+    }
+
+    // Everything moves one column to the left
+    pub fn push(&mut self, value: i32) -> Image {
+        self.samples.rotate_left(1);
+        self.samples[SIZE - 1] = Some(value);
+        self.image()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples = [None; SIZE];
+    }
+
+    pub fn image(&self) -> Image {
+        let mut image = [[0; SIZE]; SIZE];
+        for (col, sample) in self.samples.iter().enumerate() {
+            let Some(value) = sample else { continue };
+            // Bottom row is min, top row is max
+            let level = self.range.scale(*value, (SIZE as u32 - 1) * FULL);
+            let (row, part) = ((level / FULL) as usize, (level % FULL) as u8);
+            image[SIZE - 1 - row][col] = FULL as u8 - part;
+            if part > 0 {
+                image[SIZE - 2 - row][col] = part;
+            }
+        }
+        image
+    }
+}
+
+// The 16 pixels round the edge, clockwise from the top middle
+const RING: [(usize, usize); 16] = [
+    (2, 0), (3, 0), (4, 0), (4, 1), (4, 2), (4, 3), (4, 4), (3, 4),
+    (2, 4), (1, 4), (0, 4), (0, 3), (0, 2), (0, 1), (0, 0), (1, 0),
+];
+
+// Fills the ring clockwise like a gauge, max goes all the way round
+pub fn dial(value: i32, range: Range) -> Image {
+    let mut image = [[0; SIZE]; SIZE];
+    for ((x, y), brightness) in RING.iter().zip(fill(range.scale(value, RING.len() as u32 * FULL), RING.len())) {
+        image[*y][*x] = brightness;
+    }
+    image
+}
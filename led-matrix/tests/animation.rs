@@ -0,0 +1,87 @@
+use led_matrix::animation::{Animation, Animator, Image, Keyframe, Playback};
+use tiny_led_matrix::Render;
+
+// Every pixel at the same brightness, so frames are easy to tell apart
+const fn flat(brightness: u8) -> Image {
+    [[brightness; 5]; 5]
+}
+
+const FRAMES: [Keyframe; 3] = [Keyframe::image(flat(1), 2), Keyframe::image(flat(2), 1), Keyframe::image(flat(3), 3)];
+
+// The brightness shown after each tick
+fn played(animation: Animation, ticks: usize) -> Vec<u8> {
+    let mut animator = Animator::new(animation);
+    let mut shown = vec![animator.image()[0][0]];
+    for _ in 0..ticks {
+        animator.tick();
+        shown.push(animator.image()[0][0]);
+    }
+    shown
+}
+
+#[test]
+fn loop_starts_over_after_the_last_frame() {
+    let animation = Animation::new(&FRAMES, Playback::Loop);
+    assert_eq!(played(animation, 8), [1, 1, 2, 3, 3, 3, 1, 1, 2]);
+    assert_eq!(animation.len_ticks(), 6);
+}
+
+#[test]
+fn once_stops_on_the_last_frame() {
+    let animation = Animation::new(&FRAMES, Playback::Once);
+    let mut animator = Animator::new(animation);
+    for _ in 0..5 {
+        animator.tick();
+    }
+    assert!(!animator.is_finished());
+    assert!(!animator.tick());
+    assert!(animator.is_finished());
+    assert_eq!(animator.image(), flat(3));
+    assert!(!animator.tick());
+    assert_eq!(animator.index(), 2);
+}
+
+#[test]
+fn ping_pong_does_not_repeat_the_ends() {
+    let animation = Animation::new(&FRAMES, Playback::PingPong);
+    assert_eq!(played(animation, 10), [1, 1, 2, 3, 3, 3, 2, 1, 1, 2, 3]);
+    assert_eq!(animation.len_ticks(), 7);
+}
+
+// Fades in over the ticks of the frame
+fn fade(tick: u32) -> Image {
+    flat(tick as u8 * 3)
+}
+
+const FADE: [Keyframe; 2] = [Keyframe::procedural(fade, 4), Keyframe::image(flat(0), 1)];
+
+#[test]
+fn procedural_frames_are_drawn_every_tick() {
+    let animation = Animation::new(&FADE, Playback::Loop);
+    assert_eq!(played(animation, 6), [0, 3, 6, 9, 0, 0, 3]);
+}
+
+#[test]
+fn switching_restarts_and_is_playing_tells_them_apart() {
+    // Statics, so each one stays in one place
+    static FADING: Animation = Animation::new(&FADE, Playback::Loop);
+    static STEPS: Animation = Animation::new(&FRAMES, Playback::Loop);
+    let mut animator = Animator::new(STEPS);
+    animator.tick();
+    animator.tick();
+    assert!(animator.is_playing(&STEPS));
+    assert!(!animator.is_playing(&FADING));
+
+    animator.play(FADING);
+    assert!(animator.is_playing(&FADING));
+    assert_eq!(animator.index(), 0);
+    assert_eq!(animator.brightness_at(2, 2), 0);
+}
+
+#[test]
+fn empty_animation_is_blank_and_finished() {
+    let mut animator = Animator::new(Animation::new(&[], Playback::Loop));
+    assert!(animator.is_finished());
+    assert!(!animator.tick());
+    assert_eq!(animator.image(), flat(0));
+}
@@ -0,0 +1,153 @@
+use std::{cell::RefCell, convert::Infallible, rc::Rc};
+
+use embedded_hal::digital::{ErrorType, OutputPin};
+use led_matrix::{
+    driver::{Config, MatrixDriver, Polarity},
+    sprite,
+};
+
+// Every pin writes its level into the shared board, and every write is logged
+#[derive(Default)]
+struct Board {
+    rows: [bool; 5],
+    cols: [bool; 5],
+    log: Vec<(char, usize, bool)>,
+}
+
+struct MockPin {
+    board: Rc<RefCell<Board>>,
+    kind: char,
+    index: usize,
+}
+
+impl ErrorType for MockPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for MockPin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.set(true);
+        Ok(())
+    }
+}
+
+impl MockPin {
+    fn set(&self, high: bool) {
+        let mut board = self.board.borrow_mut();
+        match self.kind {
+            'r' => board.rows[self.index] = high,
+            _ => board.cols[self.index] = high,
+        }
+        board.log.push((self.kind, self.index, high));
+    }
+}
+
+fn driver(config: Config) -> (MatrixDriver<MockPin, MockPin>, Rc<RefCell<Board>>) {
+    let board = Rc::new(RefCell::new(Board::default()));
+    let pins = |kind| core::array::from_fn(|index| MockPin { board: board.clone(), kind, index });
+    let driver = MatrixDriver::new(pins('r'), pins('c'), config).unwrap();
+    (driver, board)
+}
+
+// Which LEDs are lit right now
+fn lit(board: &Board, config: &Config) -> [[bool; 5]; 5] {
+    let on = |level: bool, polarity: Polarity| level == (polarity == Polarity::ActiveHigh);
+    core::array::from_fn(|r| core::array::from_fn(|c| on(board.rows[r], config.rows) && on(board.cols[c], config.cols)))
+}
+
+// Ticks through whole frames and counts how many ticks each LED was lit
+fn on_ticks(driver: &mut MatrixDriver<MockPin, MockPin>, board: &Rc<RefCell<Board>>, frames: usize) -> [[u32; 5]; 5] {
+    let config = *driver.config();
+    let mut counts = [[0; 5]; 5];
+    for _ in 0..frames * 5 * config.levels as usize {
+        driver.tick().unwrap();
+        let board = board.borrow();
+        assert!(board.rows.iter().filter(|r| **r == (config.rows == Polarity::ActiveHigh)).count() <= 1, "two rows lit at once");
+        for (r, row) in lit(&board, &config).iter().enumerate() {
+            for (c, on) in row.iter().enumerate() {
+                counts[r][c] += *on as u32;
+            }
+        }
+    }
+    counts
+}
+
+const GRADIENT: [[u8; 5]; 5] = sprite!("
+    01234
+    56789
+    9...9
+    .....
+    ..#..
+");
+
+#[test]
+fn starts_with_everything_off() {
+    let (_driver, board) = driver(Config::MICROBIT);
+    let board = board.borrow();
+    assert_eq!(lit(&board, &Config::MICROBIT), [[false; 5]; 5]);
+    // micro:bit rows are active high and columns active low
+    assert_eq!(board.rows, [false; 5]);
+    assert_eq!(board.cols, [true; 5]);
+}
+
+#[test]
+fn every_pixel_is_on_for_its_brightness() {
+    let (mut driver, board) = driver(Config::MICROBIT);
+    driver.show(&GreyscaleRender(GRADIENT));
+    let counts = on_ticks(&mut driver, &board, 2);
+    assert_eq!(counts, GRADIENT.map(|row| row.map(|v| 2 * v as u32)));
+}
+
+#[test]
+fn inverted_polarity_lights_the_same_pixels() {
+    let config = Config { rows: Polarity::ActiveLow, cols: Polarity::ActiveHigh, ..Config::MICROBIT };
+    let (mut driver, board) = driver(config);
+    assert_eq!(board.borrow().rows, [true; 5]);
+    driver.show(&GreyscaleRender(GRADIENT));
+    assert_eq!(on_ticks(&mut driver, &board, 1), GRADIENT.map(|row| row.map(|v| v as u32)));
+}
+
+#[test]
+fn fewer_levels_scale_brightness_but_keep_dim_pixels() {
+    let config = Config { levels: 3, ..Config::MICROBIT };
+    let (mut driver, board) = driver(config);
+    driver.show_image(GRADIENT);
+    assert_eq!(driver.image()[0], [0, 1, 1, 1, 1]);
+    assert_eq!(driver.image()[1], [2, 2, 2, 3, 3]);
+    let counts = on_ticks(&mut driver, &board, 1);
+    assert_eq!(counts[1], [2, 2, 2, 3, 3]);
+}
+
+#[test]
+fn old_row_goes_dark_before_the_columns_change() {
+    let config = Config { levels: 1, ..Config::MICROBIT };
+    let (mut driver, board) = driver(config);
+    driver.show(&GreyscaleRender(GRADIENT));
+    driver.tick().unwrap();
+    board.borrow_mut().log.clear();
+    driver.tick().unwrap();
+    let log = &board.borrow().log;
+    assert_eq!(log.first(), Some(&('r', 0, false)));
+    assert_eq!(log.last(), Some(&('r', 1, true)));
+    assert!(log[1..log.len() - 1].iter().all(|(kind, _, _)| *kind == 'c'));
+}
+
+#[test]
+fn tick_period_follows_the_refresh_rate() {
+    assert_eq!(Config::MICROBIT.tick_us(), 222);
+    assert_eq!(Config { refresh_hz: 50, levels: 4, ..Config::MICROBIT }.tick_us(), 1000);
+}
+
+// Any Render will do, this one is a plain array like GreyscaleImage
+struct GreyscaleRender([[u8; 5]; 5]);
+
+impl tiny_led_matrix::Render for GreyscaleRender {
+    fn brightness_at(&self, x: usize, y: usize) -> u8 {
+        self.0[y][x]
+    }
+}
@@ -0,0 +1,61 @@
+use led_matrix::font::{Glyph, glyph, text_width};
+
+#[test]
+fn every_glyph_fits_the_matrix() {
+    let chars = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ .,:!'-+=*/%()<>#°";
+    for c in chars.chars() {
+        let g = glyph(c);
+        assert!((1..=5).contains(&g.width()), "{c}");
+        if c != ' ' {
+            assert!((0..g.width()).any(|col| g.column(col) != 0), "{c} is blank");
+        }
+        assert_ne!(g, glyph('?'), "{c} has no glyph");
+    }
+}
+
+#[test]
+fn lower_case_is_upper_case() {
+    for (lower, upper) in ('a'..='z').zip('A'..='Z') {
+        assert_eq!(glyph(lower), glyph(upper));
+    }
+}
+
+#[test]
+fn unknown_characters_become_a_question_mark() {
+    assert_eq!(glyph('~'), glyph('?'));
+    assert_eq!(glyph('ß'), glyph('?'));
+}
+
+#[test]
+fn columns_read_top_to_bottom() {
+    let one = glyph('1');
+    assert_eq!(one.width(), 3);
+    assert_eq!(one.column(0), 0b10010);
+    assert_eq!(one.column(1), 0b11111);
+    assert_eq!(one.column(2), 0b10000);
+    assert!(one.is_lit(1, 0));
+    assert!(!one.is_lit(0, 0));
+    assert!(!one.is_lit(3, 4));
+}
+
+#[test]
+fn parse_reads_ascii_art() {
+    let g = Glyph::parse(["#.", ".#", "#.", ".#", "##"]);
+    assert_eq!(g.width(), 2);
+    assert_eq!(g.column(0), 0b10101);
+    assert_eq!(g.column(1), 0b11010);
+}
+
+#[test]
+#[should_panic(expected = "different widths")]
+fn parse_rejects_ragged_rows() {
+    Glyph::parse(["#.", "#", "#.", "#.", "#."]);
+}
+
+#[test]
+fn text_width_counts_gaps() {
+    assert_eq!(text_width(""), 0);
+    assert_eq!(text_width("1"), 3);
+    assert_eq!(text_width("11"), 7);
+    assert_eq!(text_width("37"), 9);
+}
@@ -0,0 +1,106 @@
+use embedded_graphics::{
+    pixelcolor::Gray4,
+    prelude::*,
+    primitives::{Circle, Line, PrimitiveStyle, Rectangle},
+    text::{Baseline, Text},
+};
+use led_matrix::{
+    graphics::{Framebuffer, GlyphStyle, brightness},
+    sprite,
+};
+use tiny_led_matrix::Render;
+
+#[test]
+fn gray_levels_scale_to_display_brightness() {
+    assert_eq!(brightness(Gray4::BLACK), 0);
+    assert_eq!(brightness(Gray4::new(1)), 1);
+    assert_eq!(brightness(Gray4::new(8)), 5);
+    assert_eq!(brightness(Gray4::WHITE), 9);
+}
+
+#[test]
+fn lines_clip_at_the_edge() {
+    let mut fb = Framebuffer::new();
+    Line::new(Point::new(-2, -2), Point::new(6, 6))
+        .into_styled(PrimitiveStyle::with_stroke(Gray4::WHITE, 1))
+        .draw(&mut fb)
+        .unwrap();
+    // Drawn last, so it wins the corner
+    Line::new(Point::new(0, 4), Point::new(4, 4))
+        .into_styled(PrimitiveStyle::with_stroke(Gray4::new(5), 1))
+        .draw(&mut fb)
+        .unwrap();
+    assert_eq!(fb.image(), sprite!("
+        #....
+        .#...
+        ..#..
+        ...#.
+        33333
+    "));
+}
+
+#[test]
+fn rectangle_outline_and_fill() {
+    let mut fb = Framebuffer::new();
+    Rectangle::new(Point::new(0, 0), Size::new(5, 5))
+        .into_styled(PrimitiveStyle::with_stroke(Gray4::WHITE, 1))
+        .draw(&mut fb)
+        .unwrap();
+    Rectangle::new(Point::new(2, 2), Size::new(2, 2))
+        .into_styled(PrimitiveStyle::with_fill(Gray4::new(10)))
+        .draw(&mut fb)
+        .unwrap();
+    assert_eq!(fb.image(), sprite!("
+        #####
+        #...#
+        #.66#
+        #.66#
+        #####
+    "));
+}
+
+#[test]
+fn circle_outline() {
+    let mut fb = Framebuffer::new();
+    Circle::new(Point::new(0, 0), 5)
+        .into_styled(PrimitiveStyle::with_stroke(Gray4::WHITE, 1))
+        .draw(&mut fb)
+        .unwrap();
+    assert_eq!(fb.image(), sprite!("
+        .###.
+        ##.##
+        #...#
+        ##.##
+        .###.
+    "));
+}
+
+#[test]
+fn text_uses_the_matrix_font() {
+    let mut fb = Framebuffer::new();
+    let style = GlyphStyle::new(Gray4::WHITE);
+    let next = Text::with_baseline("1", Point::new(1, 0), style, Baseline::Top).draw(&mut fb).unwrap();
+    assert_eq!(next, Point::new(5, 0));
+    assert_eq!(fb.image(), sprite!("
+        ..#..
+        .##..
+        ..#..
+        ..#..
+        .###.
+    "));
+
+    // The default alphabetic baseline is the bottom row
+    let mut fb = Framebuffer::new();
+    Text::new("-", Point::new(1, 4), style).draw(&mut fb).unwrap();
+    assert_eq!(fb.image()[2], [0, 9, 9, 9, 0]);
+}
+
+#[test]
+fn framebuffer_renders_like_its_image() {
+    let mut fb = Framebuffer::new();
+    fb.clear(Gray4::new(3)).unwrap();
+    Pixel(Point::new(4, 1), Gray4::WHITE).draw(&mut fb).unwrap();
+    assert_eq!(fb.brightness_at(4, 1), 9);
+    assert_eq!(fb.brightness_at(1, 4), 2);
+    assert_eq!(fb.image()[1][4], 9);
+}
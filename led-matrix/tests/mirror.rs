@@ -0,0 +1,51 @@
+use led_matrix::{
+    mirror::{FRAME_LEN, MirrorDecoder, MirrorFrame},
+    sprite,
+    sprites::HEART,
+};
+
+const GRADIENT: [[u8; 5]; 5] = sprite!("
+    01234
+    56789
+    98765
+    43210
+    #.#.#
+");
+
+fn decode_all(bytes: &[u8]) -> Vec<MirrorFrame> {
+    let mut decoder = MirrorDecoder::new();
+    bytes.iter().filter_map(|byte| decoder.feed(*byte)).collect()
+}
+
+#[test]
+fn frames_come_back_as_they_were_sent() {
+    let frames = [MirrorFrame::new(0, GRADIENT), MirrorFrame::new(1, HEART), MirrorFrame::new(0xa5, [[0; 5]; 5])];
+    let bytes: Vec<u8> = frames.iter().flat_map(|frame| frame.encode()).collect();
+    assert_eq!(bytes.len(), 3 * FRAME_LEN);
+    assert_eq!(decode_all(&bytes), frames);
+}
+
+#[test]
+fn too_bright_is_sent_as_full() {
+    let frame = MirrorFrame::new(3, [[12; 5]; 5]);
+    assert_eq!(decode_all(&frame.encode())[0].image, [[9; 5]; 5]);
+}
+
+#[test]
+fn garbage_between_frames_is_skipped() {
+    let mut bytes = b"boot\r\n\xa5\x01".to_vec();
+    bytes.extend(MirrorFrame::new(7, GRADIENT).encode());
+    bytes.extend([0xa5, 0xa5, 0x00]);
+    bytes.extend(MirrorFrame::new(8, HEART).encode());
+    let sequences: Vec<u8> = decode_all(&bytes).iter().map(|frame| frame.sequence).collect();
+    assert_eq!(sequences, [7, 8]);
+}
+
+#[test]
+fn broken_frames_are_dropped() {
+    let mut broken = MirrorFrame::new(1, GRADIENT).encode();
+    broken[5] ^= 0x10;
+    let mut bytes = broken.to_vec();
+    bytes.extend(MirrorFrame::new(2, HEART).encode());
+    assert_eq!(decode_all(&bytes), [MirrorFrame::new(2, HEART)]);
+}
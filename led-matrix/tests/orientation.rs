@@ -0,0 +1,102 @@
+use led_matrix::{
+    orientation::{Orientation, OrientationTracker, Rotation},
+    sprite,
+    sprites::ARROW_N,
+};
+use tiny_led_matrix::Render;
+
+const CORNER: [[u8; 5]; 5] = sprite!("
+    #....
+    .....
+    .....
+    .....
+    .....
+");
+
+const ALL: [Orientation; 8] = [
+    Orientation::new(Rotation::Deg0, false),
+    Orientation::new(Rotation::Deg90, false),
+    Orientation::new(Rotation::Deg180, false),
+    Orientation::new(Rotation::Deg270, false),
+    Orientation::new(Rotation::Deg0, true),
+    Orientation::new(Rotation::Deg90, true),
+    Orientation::new(Rotation::Deg180, true),
+    Orientation::new(Rotation::Deg270, true),
+];
+
+#[test]
+fn rotations_go_clockwise() {
+    let turned = |rotation| Orientation::new(rotation, false).apply(&CORNER);
+    assert_eq!(turned(Rotation::Deg90)[0][4], 9);
+    assert_eq!(turned(Rotation::Deg180)[4][4], 9);
+    assert_eq!(turned(Rotation::Deg270)[4][0], 9);
+    assert_eq!(Orientation::new(Rotation::Deg90, false).apply(&ARROW_N), led_matrix::sprites::ARROW_E);
+}
+
+#[test]
+fn mirroring_flips_left_and_right_before_turning() {
+    assert_eq!(Orientation::new(Rotation::Deg0, true).apply(&CORNER)[0][4], 9);
+    assert_eq!(Orientation::new(Rotation::Deg90, true).apply(&CORNER)[4][4], 9);
+}
+
+#[test]
+fn inverse_undoes_every_orientation() {
+    for orientation in ALL {
+        assert_eq!(orientation.inverse().apply(&orientation.apply(&ARROW_N)), ARROW_N, "{orientation:?}");
+        let (dx, dy) = orientation.map_offset(1, 0);
+        assert_eq!(orientation.inverse().map_offset(dx, dy), (1, 0), "{orientation:?}");
+    }
+}
+
+struct Plain([[u8; 5]; 5]);
+
+impl Render for Plain {
+    fn brightness_at(&self, x: usize, y: usize) -> u8 {
+        self.0[y][x]
+    }
+}
+
+#[test]
+fn render_matches_apply() {
+    for orientation in ALL {
+        let turned = orientation.apply(&ARROW_N);
+        let plain = Plain(ARROW_N);
+        let rendered = orientation.render(&plain);
+        for (y, row) in turned.iter().enumerate() {
+            for (x, brightness) in row.iter().enumerate() {
+                assert_eq!(rendered.brightness_at(x, y), *brightness, "{orientation:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn tracker_turns_the_bottom_to_the_edge_pointing_down() {
+    let settled = |x, y, z| {
+        let mut tracker = OrientationTracker::new(400, 3);
+        (0..3).map(|_| tracker.update(x, y, z)).last().unwrap()
+    };
+    assert_eq!(settled(0, 1000, -100), Orientation::new(Rotation::Deg0, false));
+    assert_eq!(settled(900, 0, 0), Orientation::new(Rotation::Deg270, false));
+    assert_eq!(settled(0, -900, 0), Orientation::new(Rotation::Deg180, false));
+    assert_eq!(settled(-900, 100, 0), Orientation::new(Rotation::Deg90, false));
+    assert_eq!(settled(0, 0, 1000), Orientation::new(Rotation::Deg0, true));
+}
+
+#[test]
+fn tracker_waits_for_a_steady_reading() {
+    let mut tracker = OrientationTracker::new(400, 3);
+    let turned = Orientation::new(Rotation::Deg270, false);
+    // A bump is not enough
+    tracker.update(900, 0, 0);
+    tracker.update(900, 0, 0);
+    assert_eq!(tracker.update(0, 1000, 0), Orientation::UPRIGHT);
+    tracker.update(900, 0, 0);
+    tracker.update(900, 0, 0);
+    assert_eq!(tracker.update(900, 0, 0), turned);
+    // Near 45 degrees and lying flat both keep it
+    for _ in 0..10 {
+        assert_eq!(tracker.update(600, 650, 0), turned);
+        assert_eq!(tracker.update(50, 80, -1000), turned);
+    }
+}
@@ -0,0 +1,79 @@
+use core::fmt::Write;
+
+use led_matrix::scroll::ScrollingText;
+use tiny_led_matrix::Render;
+
+// One string per row, '#' for lit
+fn rows(frame: [[u8; 5]; 5]) -> [String; 5] {
+    frame.map(|row| row.iter().map(|v| if *v > 0 { '#' } else { '.' }).collect())
+}
+
+#[test]
+fn starts_blank_and_enters_from_the_right() {
+    let mut text: ScrollingText<8> = ScrollingText::new("1");
+    assert_eq!(rows(text.frame()), [".....", ".....", ".....", ".....", "....."]);
+    text.advance();
+    assert_eq!(rows(text.frame()), [".....", "....#", ".....", ".....", "....#"]);
+    text.advance();
+    text.advance();
+    assert_eq!(rows(text.frame()), ["...#.", "..##.", "...#.", "...#.", "..###"]);
+}
+
+#[test]
+fn finishes_when_the_text_has_left() {
+    let mut text: ScrollingText<8> = ScrollingText::new("1");
+    assert_eq!(text.len(), 8);
+    let mut steps = 0;
+    while text.advance() {
+        steps += 1;
+    }
+    assert_eq!(steps, 8);
+    assert!(text.is_finished());
+    assert_eq!(text.frame(), [[0; 5]; 5]);
+    assert!(!text.advance());
+
+    text.restart();
+    assert!(!text.is_finished());
+}
+
+#[test]
+fn glyphs_are_separated_by_a_blank_column() {
+    let mut text: ScrollingText<8> = ScrollingText::new("11");
+    for _ in 0..7 {
+        text.advance();
+    }
+    // Last column of the first '1', the gap, then the second '1'
+    assert_eq!(rows(text.frame()), ["...#.", "..##.", "...#.", "...#.", "#.###"]);
+}
+
+#[test]
+fn builds_text_with_write() {
+    let mut text: ScrollingText<16> = ScrollingText::new("");
+    assert!(text.is_empty());
+    write!(text, "SCORE {}", 37).unwrap();
+    assert_eq!(text.text(), "SCORE 37");
+
+    let mut short: ScrollingText<4> = ScrollingText::new("");
+    assert!(write!(short, "SCORE {}", 37).is_err());
+}
+
+#[test]
+fn long_text_is_cut_off() {
+    let text: ScrollingText<4> = ScrollingText::new("HEADING");
+    assert_eq!(text.text(), "HEAD");
+}
+
+#[test]
+fn render_matches_the_frame() {
+    let mut text: ScrollingText<16> = ScrollingText::new("270°");
+    text.set_brightness(5);
+    while text.advance() {
+        let frame = text.frame();
+        for (y, row) in frame.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                assert_eq!(text.brightness_at(x, y), *value);
+                assert!(*value == 0 || *value == 5);
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use led_matrix::{
+    animation::Image,
+    sprite,
+    sprite::parse,
+    sprites::{ARROWS, COMPASS_8, COMPASS_16, NEEDLE_NE},
+};
+
+// Quarter turn clockwise
+fn rotate(image: Image) -> Image {
+    let mut rotated = [[0; 5]; 5];
+    for (r, row) in image.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            rotated[c][4 - r] = *value;
+        }
+    }
+    rotated
+}
+
+#[test]
+fn parses_dots_hashes_and_digits() {
+    const IMAGE: Image = sprite!("..#..\n.#3#.\n#.6.#\n..1..\n..0..");
+    assert_eq!(IMAGE, [[0, 0, 9, 0, 0], [0, 9, 3, 9, 0], [9, 0, 6, 0, 9], [0, 0, 1, 0, 0], [0, 0, 0, 0, 0]]);
+}
+
+#[test]
+fn indentation_and_blank_lines_are_ignored() {
+    let indented = sprite!("
+
+        #....
+        .#...
+        ..#..
+        ...#.
+        ....#
+    ");
+    assert_eq!(indented, parse("#....\n.#...\n..#..\n...#.\n....#"));
+}
+
+#[test]
+#[should_panic(expected = "not 5 wide")]
+fn short_row_is_refused() {
+    parse("....\n.....\n.....\n.....\n.....");
+}
+
+#[test]
+#[should_panic(expected = "does not have 5 rows")]
+fn missing_row_is_refused() {
+    parse(".....\n.....\n.....\n.....");
+}
+
+#[test]
+#[should_panic(expected = "unknown character")]
+fn unknown_character_is_refused() {
+    parse(".....\n..o..\n.....\n.....\n.....");
+}
+
+#[test]
+fn arrows_turn_clockwise() {
+    for i in 0..4 {
+        assert_eq!(rotate(ARROWS[2 * i]), ARROWS[(2 * i + 2) % 8]);
+        assert_eq!(rotate(ARROWS[2 * i + 1]), ARROWS[(2 * i + 3) % 8]);
+    }
+}
+
+#[test]
+fn compass_needles_point_to_the_edge_clockwise() {
+    // The 16 cells around the edge, clockwise from the top middle
+    let edge = [
+        (0, 2), (0, 3), (0, 4), (1, 4), (2, 4), (3, 4), (4, 4), (4, 3),
+        (4, 2), (4, 1), (4, 0), (3, 0), (2, 0), (1, 0), (0, 0), (0, 1),
+    ];
+    for (needle, (row, col)) in COMPASS_16.iter().zip(edge) {
+        assert_eq!(needle[row][col], 9);
+        assert_eq!(needle[2][2], 4);
+        assert_eq!(needle.iter().flatten().filter(|v| **v == 9).count(), 1);
+    }
+    for i in 0..8 {
+        assert_eq!(COMPASS_8[i], COMPASS_16[2 * i]);
+    }
+    assert_eq!(COMPASS_8[1], NEEDLE_NE);
+}
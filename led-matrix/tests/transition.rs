@@ -0,0 +1,85 @@
+use led_matrix::{
+    animation::Image,
+    sprite,
+    transition::{Direction, Effect, Transition},
+};
+use tiny_led_matrix::Render;
+
+const BLANK: Image = [[0; 5]; 5];
+const FULL: Image = [[9; 5]; 5];
+
+// A different brightness per column, so slides can be followed
+const COLUMNS: Image = sprite!("
+    12345
+    12345
+    12345
+    12345
+    12345
+");
+
+fn lit(image: &Image) -> usize {
+    image.iter().flatten().filter(|&&v| v > 0).count()
+}
+
+#[test]
+fn starts_on_the_old_image_and_ends_on_the_new_one() {
+    for effect in [
+        Effect::Crossfade,
+        Effect::Wipe(Direction::Up),
+        Effect::Slide(Direction::Left),
+        Effect::Dissolve { seed: 7 },
+    ] {
+        let mut transition = Transition::new(COLUMNS, FULL, effect, 4);
+        assert_eq!(transition.current(), COLUMNS);
+        let frames: Vec<Image> = core::iter::from_fn(|| transition.next_frame()).collect();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[3], FULL);
+        assert!(transition.is_finished());
+        assert_eq!(transition.brightness_at(0, 0), 9);
+    }
+}
+
+#[test]
+fn crossfade_blends_evenly() {
+    let transition = Transition::new(BLANK, FULL, Effect::Crossfade, 3);
+    let shown: Vec<u8> = (0..=3).map(|n| transition.frame_at(n)[2][2]).collect();
+    assert_eq!(shown, [0, 3, 6, 9]);
+}
+
+#[test]
+fn wipe_reveals_from_the_side_it_moves_away_from() {
+    let transition = Transition::new(BLANK, FULL, Effect::Wipe(Direction::Right), 5);
+    assert_eq!(transition.frame_at(2)[0], [9, 9, 0, 0, 0]);
+    let transition = Transition::new(BLANK, FULL, Effect::Wipe(Direction::Up), 5);
+    let column: Vec<u8> = transition.frame_at(1).iter().map(|row| row[0]).collect();
+    assert_eq!(column, [0, 0, 0, 0, 9]);
+}
+
+#[test]
+fn slide_pushes_the_old_image_out() {
+    let transition = Transition::new(COLUMNS, BLANK, Effect::Slide(Direction::Left), 5);
+    assert_eq!(transition.frame_at(2)[0], [3, 4, 5, 0, 0]);
+    let transition = Transition::new(BLANK, COLUMNS, Effect::Slide(Direction::Right), 5);
+    assert_eq!(transition.frame_at(2)[0], [4, 5, 0, 0, 0]);
+}
+
+#[test]
+fn dissolve_switches_more_pixels_every_frame() {
+    let transition = Transition::new(BLANK, FULL, Effect::Dissolve { seed: 42 }, 5);
+    let counts: Vec<usize> = (0..=5).map(|n| lit(&transition.frame_at(n))).collect();
+    assert_eq!(counts, [0, 5, 10, 15, 20, 25]);
+    // Pixels that switched stay switched
+    for n in 1..5 {
+        let (before, after) = (transition.frame_at(n), transition.frame_at(n + 1));
+        assert!(before.iter().flatten().zip(after.iter().flatten()).all(|(b, a)| *b == 0 || *a == 9));
+    }
+}
+
+#[test]
+fn dissolve_order_depends_on_the_seed() {
+    let a = Transition::new(BLANK, FULL, Effect::Dissolve { seed: 1 }, 5);
+    let b = Transition::new(BLANK, FULL, Effect::Dissolve { seed: 1 }, 5);
+    let c = Transition::new(BLANK, FULL, Effect::Dissolve { seed: 2 }, 5);
+    assert_eq!(a.frame_at(2), b.frame_at(2));
+    assert_ne!(a.frame_at(2), c.frame_at(2));
+}
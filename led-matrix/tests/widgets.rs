@@ -0,0 +1,116 @@
+use led_matrix::{
+    sprite,
+    widgets::{PeakMeter, Range, Sparkline, centre_meter, dial, horizontal_bars, vertical_bars},
+};
+
+const PERCENT: Range = Range::new(0, 100);
+
+fn column(image: &[[u8; 5]; 5], col: usize) -> [u8; 5] {
+    image.map(|row| row[col])
+}
+
+#[test]
+fn range_clamps_and_rounds() {
+    assert_eq!(PERCENT.scale(-20, 45), 0);
+    assert_eq!(PERCENT.scale(50, 45), 23);
+    assert_eq!(PERCENT.scale(250, 45), 45);
+    assert_eq!(Range::new(-1000, 1000).scale(0, 8), 4);
+    assert_eq!(Range::new(5, 5).scale(5, 8), 0);
+}
+
+#[test]
+fn bars_fill_with_a_partly_lit_end() {
+    let image = vertical_bars(&[100, 50, 0], PERCENT);
+    assert_eq!(column(&image, 0), [9; 5]);
+    assert_eq!(column(&image, 1), [0, 0, 5, 9, 9]);
+    assert_eq!(column(&image, 2), [0; 5]);
+    assert_eq!(column(&image, 3), [0; 5]);
+    let image = horizontal_bars(&[40], PERCENT);
+    assert_eq!(image[0], [9, 9, 0, 0, 0]);
+    assert_eq!(image[1], [0; 5]);
+}
+
+#[test]
+fn centre_meter_goes_both_ways() {
+    let range = Range::new(-1000, 500);
+    assert_eq!(centre_meter(0, range)[0], [0, 0, 2, 0, 0]);
+    assert_eq!(centre_meter(500, range)[0], [0, 0, 2, 9, 9]);
+    assert_eq!(centre_meter(-500, range)[4], [0, 9, 2, 0, 0]);
+    assert_eq!(centre_meter(-5000, range)[2], [9, 9, 2, 0, 0]);
+}
+
+#[test]
+fn peak_holds_then_sinks() {
+    let mut meter = PeakMeter::new(PERCENT, 2, 9);
+    meter.update(100);
+    let image = meter.update(20);
+    assert_eq!(column(&image, 0), [9, 0, 0, 0, 9]);
+    meter.update(20);
+    assert_eq!(meter.peak(), 45);
+    meter.update(20);
+    assert_eq!(meter.peak(), 36);
+    for _ in 0..10 {
+        meter.update(20);
+    }
+    // Down to the bar, but not below it
+    assert_eq!(meter.peak(), 9);
+    assert_eq!(column(&meter.image(), 0), [0, 0, 0, 0, 9]);
+}
+
+#[test]
+fn sparkline_scrolls_in_from_the_right() {
+    let mut line = Sparkline::new(PERCENT);
+    line.push(0);
+    let image = line.push(100);
+    assert_eq!(image, sprite!("
+        ....#
+        .....
+        .....
+        .....
+        ...#.
+    "));
+    for value in [25, 50, 75, 100] {
+        line.push(value);
+    }
+    assert_eq!(line.image(), sprite!("
+        #...#
+        ...#.
+        ..#..
+        .#...
+        .....
+    "));
+    line.clear();
+    for value in [0, 25, 50, 75, 100] {
+        line.push(value);
+    }
+    assert_eq!(line.image(), sprite!("
+        ....#
+        ...#.
+        ..#..
+        .#...
+        #....
+    "));
+    // Between two rows, still closer to the bottom one
+    assert_eq!(column(&line.push(12), 4), [0, 0, 0, 4, 5]);
+}
+
+#[test]
+fn dial_fills_clockwise_from_the_top() {
+    assert_eq!(dial(0, PERCENT), [[0; 5]; 5]);
+    assert_eq!(dial(25, PERCENT), sprite!("
+        ..###
+        ....#
+        .....
+        .....
+        .....
+    "));
+    // A bit more than a pixel past that
+    assert_eq!(dial(30, PERCENT)[2][4], 7);
+    assert_eq!(dial(100, PERCENT), sprite!("
+        #####
+        #...#
+        #...#
+        #...#
+        #####
+    "));
+}
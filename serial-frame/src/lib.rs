@@ -0,0 +1,77 @@
+#![no_std]
+
+// Fixed length frames for a serial line, shared by the versus link, the crash snapshot and the
+// display mirror: SYNC | payload | crc8 over everything before it. A crate of its own so the
+// display library does not have to pull in the game for it.
+// The sync byte may turn up in the payload or the crc too, so the decoder tries again from the
+// next sync byte whenever a frame does not check out.
+
+pub const SYNC: u8 = 0xa5;
+
+// CRC-8 with polynomial 0x07, one byte is plenty for frames this short
+pub fn crc8(bytes: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for byte in bytes {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+// Puts in the sync byte and the crc around a payload that is already in place
+pub fn seal(frame: &mut [u8]) {
+    let last = frame.len() - 1;
+    frame[0] = SYNC;
+    frame[last] = crc8(&frame[..last]);
+}
+
+pub fn is_sealed(frame: &[u8]) -> bool {
+    let Some((crc, rest)) = frame.split_last() else {
+        return false;
+    };
+    rest.first() == Some(&SYNC) && crc8(rest) == *crc
+}
+
+// Collects bytes from the line into frames, garbage and broken frames are skipped
+#[derive(Debug)]
+pub struct FrameDecoder<const LEN: usize> {
+    frame: [u8; LEN],
+    len: usize,
+}
+
+impl<const LEN: usize> Default for FrameDecoder<LEN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const LEN: usize> FrameDecoder<LEN> {
+    pub const fn new() -> Self {
+        Self { frame: [0; LEN], len: 0 }
+    }
+
+    // A whole frame with the right sync byte and crc, what the payload means is up to the caller
+    pub fn feed(&mut self, byte: u8) -> Option<[u8; LEN]> {
+        if self.len == 0 && byte != SYNC {
+            return None;
+        }
+        self.frame[self.len] = byte;
+        self.len += 1;
+        if self.len < LEN {
+            return None;
+        }
+        self.len = 0;
+        if is_sealed(&self.frame) {
+            return Some(self.frame);
+        }
+        // Maybe the sync byte was part of a broken frame, look for the next one in what came after it.
+        // That can not be a whole frame yet, so nothing comes out of this.
+        let rest = self.frame;
+        for byte in rest[1..].iter() {
+            self.feed(*byte);
+        }
+        None
+    }
+}
@@ -0,0 +1,48 @@
+use serial_frame::{FrameDecoder, SYNC, crc8, is_sealed, seal};
+
+fn sealed(payload: [u8; 3]) -> [u8; 5] {
+    let mut frame = [0, payload[0], payload[1], payload[2], 0];
+    seal(&mut frame);
+    frame
+}
+
+fn feed_all<const LEN: usize>(decoder: &mut FrameDecoder<LEN>, bytes: &[u8]) -> Vec<[u8; LEN]> {
+    bytes.iter().filter_map(|byte| decoder.feed(*byte)).collect()
+}
+
+#[test]
+fn crc8_check_value() {
+    // The usual check for CRC-8 with polynomial 0x07
+    assert_eq!(crc8(b"123456789"), 0xf4);
+    assert_eq!(crc8(&[]), 0);
+}
+
+#[test]
+fn sealed_frame_checks_out() {
+    let frame = sealed([1, 2, 3]);
+    assert_eq!(frame[0], SYNC);
+    assert!(is_sealed(&frame));
+    let mut broken = frame;
+    broken[2] ^= 0x10;
+    assert!(!is_sealed(&broken));
+    assert!(!is_sealed(&[]));
+}
+
+#[test]
+fn garbage_before_a_frame_is_skipped() {
+    let mut decoder: FrameDecoder<5> = FrameDecoder::new();
+    let frame = sealed([7, 8, 9]);
+    let mut bytes = vec![0x00, 0x13, 0x37];
+    bytes.extend_from_slice(&frame);
+    assert_eq!(feed_all(&mut decoder, &bytes), [frame]);
+}
+
+#[test]
+fn sync_byte_in_a_broken_frame_does_not_lose_the_next_one() {
+    let mut decoder: FrameDecoder<5> = FrameDecoder::new();
+    let frame = sealed([SYNC, 4, 5]);
+    // Cut off after the sync byte in the payload, the real frame follows right after
+    let mut bytes = vec![SYNC, 1, SYNC];
+    bytes.extend_from_slice(&frame);
+    assert_eq!(feed_all(&mut decoder, &bytes), [frame]);
+}
@@ -0,0 +1,69 @@
+// Plays many games with each autopilot strategy and prints how they did.
+// Usage: cargo run --release --example autopilot [games]
+
+use snake_core::{
+    Game,
+    autopilot::{Autopilot, Strategy},
+    level::LEVELS,
+    movement::GameStatus,
+    rules::GameRules,
+};
+
+// Long enough for a Hamiltonian game on 8x8, a game still going after that counts as stuck
+const MAX_STEPS: usize = 20_000;
+
+struct Results {
+    won: usize,
+    lost: usize,
+    stuck: usize,
+    score: u64,
+    steps: u64,
+}
+
+fn play<const W: usize, const H: usize>(
+    games: u32,
+    strategy: Strategy,
+    rules: GameRules,
+    levels: &'static [snake_core::level::Level<W, H>],
+) -> Results {
+    let mut results = Results { won: 0, lost: 0, stuck: 0, score: 0, steps: 0 };
+    for seed in 1..=games {
+        let mut game: Game<W, H> = Game::with_levels(seed, rules, levels);
+        let mut autopilot = Autopilot::new(strategy);
+        let mut steps = 0;
+        while game.status == GameStatus::Ongoing && steps < MAX_STEPS {
+            game.step(autopilot.next_turn(&game));
+            steps += 1;
+        }
+        match game.status {
+            GameStatus::Won => results.won += 1,
+            GameStatus::Lost => results.lost += 1,
+            GameStatus::Ongoing => results.stuck += 1,
+        }
+        results.score += game.score as u64;
+        results.steps += steps as u64;
+    }
+    results
+}
+
+fn report(name: &str, games: u32, results: Results) {
+    println!(
+        "{name:<28} won {:>5.1}%  lost {:>5}  stuck {:>5}  avg score {:>6.2}  avg steps {:>7.1}",
+        100.0 * results.won as f64 / games as f64,
+        results.lost,
+        results.stuck,
+        results.score as f64 / games as f64,
+        results.steps as f64 / games as f64,
+    );
+}
+
+fn main() {
+    let games = std::env::args().nth(1).and_then(|a| a.parse().ok()).unwrap_or(1000);
+    println!("{games} games per line");
+    for strategy in [Strategy::ShortestPath, Strategy::Hamiltonian] {
+        report(&format!("{strategy:?} 5x5 classic"), games, play::<5, 5>(games, strategy, GameRules::CLASSIC, &[]));
+        report(&format!("{strategy:?} 5x5 walls"), games, play::<5, 5>(games, strategy, GameRules::WALLS, &[]));
+        report(&format!("{strategy:?} 5x5 levels"), games, play::<5, 5>(games, strategy, GameRules::LEVELS, &LEVELS));
+        report(&format!("{strategy:?} 8x8 walls"), games, play::<8, 8>(games, strategy, GameRules::WALLS, &[]));
+    }
+}
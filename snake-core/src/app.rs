@@ -0,0 +1,120 @@
+
+// What the board is doing around the game: the title screen, playing, paused and the end of a game.
+// Only button presses move it along, what to show and when to step the game is up to the caller.
+//
+//  Title --B--> Playing --A+B--> Paused --A+B--> Playing
+//  Title --A--> Title with the next difficulty
+//  Playing --game_over--> GameOver --any--> Title
+
+use crate::{input::Press, movement::Turn, speed::SpeedCurve};
+
+// Which speed curve the game runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    // Speeds up the longer the snake survives, eating or not
+    Survival,
+}
+
+const SURVIVAL_STEPS: SpeedCurve = SpeedCurve::stepped(&[800, 650, 520, 420, 350, 300, 260, 230, 200, 180]);
+
+impl Difficulty {
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Survival,
+            Difficulty::Survival => Difficulty::Easy,
+        }
+    }
+
+    pub fn speed_curve(self) -> SpeedCurve {
+        match self {
+            Difficulty::Easy => SpeedCurve::Linear { start_ms: 1000, step_ms: 100, min_ms: 400 },
+            Difficulty::Normal => SpeedCurve::CLASSIC,
+            Difficulty::Hard => SpeedCurve::Exponential { start_ms: 700, percent: 85, min_ms: 150 },
+            Difficulty::Survival => SpeedCurve::TimeBased { every_steps: 30, curve: &SURVIVAL_STEPS },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppState {
+    Title,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+// What the caller has to do after a press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Nothing,
+    Steer(Turn),
+    ChangeDifficulty(Difficulty),
+    StartGame(Difficulty),
+    Pause,
+    Resume,
+    ShowTitle,
+}
+
+pub struct App {
+    state: AppState,
+    difficulty: Difficulty,
+}
+
+impl App {
+    pub const fn new(difficulty: Difficulty) -> Self {
+        Self { state: AppState::Title, difficulty }
+    }
+
+    pub fn state(&self) -> AppState {
+        self.state
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn press(&mut self, press: Press) -> Action {
+        match (self.state, press) {
+            (AppState::Title, Press::A) => {
+                self.difficulty = self.difficulty.next();
+                Action::ChangeDifficulty(self.difficulty)
+            },
+            (AppState::Title, Press::B) => {
+                self.state = AppState::Playing;
+                Action::StartGame(self.difficulty)
+            },
+            (AppState::Playing, Press::AB) => {
+                self.state = AppState::Paused;
+                Action::Pause
+            },
+            (AppState::Playing, press) => Action::Steer(press.turn()),
+            (AppState::Paused, Press::AB) => {
+                self.state = AppState::Playing;
+                Action::Resume
+            },
+            (AppState::GameOver, _) => {
+                self.state = AppState::Title;
+                Action::ShowTitle
+            },
+            (AppState::Title, Press::AB) | (AppState::Paused, _) => Action::Nothing,
+        }
+    }
+
+    // The game being played is won or lost
+    pub fn game_over(&mut self) {
+        if self.state == AppState::Playing {
+            self.state = AppState::GameOver;
+        }
+    }
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new(Difficulty::Normal)
+    }
+}
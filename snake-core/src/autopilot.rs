@@ -0,0 +1,269 @@
+
+// A player for the attract mode and for trying out rules on the host.
+// It looks ahead by stepping clones of the game, so whatever the rules do the autopilot sees too.
+
+use crate::{
+    Game,
+    coords::Coords,
+    input::turn_towards,
+    movement::{Direction, GameStatus, Turn},
+    rules::Edges,
+};
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    // Shortest way to the food, but only if the tail can still be reached after eating it.
+    // Otherwise it follows its tail until the way to the food is safe.
+    ShortestPath,
+    // Walks a fixed cycle through every cell, slow but it can not lose. Boards without a cycle
+    // (odd by odd without wrapping, levels with walls) are played with ShortestPath instead.
+    Hamiltonian,
+}
+
+pub struct Autopilot {
+    strategy: Strategy,
+    // Steps since the score went up, chasing the tail for too long goes round in circles forever
+    hungry: usize,
+    score: u16,
+}
+
+impl Autopilot {
+    pub fn new(strategy: Strategy) -> Self {
+        Self { strategy, hungry: 0, score: 0 }
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    pub fn next_turn<const W: usize, const H: usize>(&mut self, game: &Game<W, H>) -> Turn {
+        if game.score != self.score {
+            self.score = game.score;
+            self.hungry = 0;
+        }
+        self.hungry += 1;
+
+        let heading = game.snake().direction;
+        if self.strategy == Strategy::Hamiltonian
+            && let Some(cycle) = hamiltonian_cycle(game)
+        {
+            let head = game.snake().head;
+            let direction = cycle[head.row as usize][head.col as usize];
+            if after_move(game, direction).is_some() {
+                return turn_towards(heading, direction);
+            }
+        }
+        // After going round the whole board a couple of times without eating the food is
+        // worth the risk of not reaching the tail
+        let risky = self.hungry > 2 * W * H;
+        turn_towards(heading, shortest_path(game, risky).unwrap_or(heading))
+    }
+}
+
+fn shortest_path<const W: usize, const H: usize>(game: &Game<W, H>, risky: bool) -> Option<Direction> {
+    let paths = Paths::search(game);
+
+    // Straight to the food if the snake can still get out after eating
+    if let Some(direction) = paths.first_step(game.food()) {
+        let fed = eat_along_shortest_path(game);
+        if fed.status == GameStatus::Won || (fed.status == GameStatus::Ongoing && (risky || tail_reachable(&fed))) {
+            return Some(direction);
+        }
+    }
+
+    // Otherwise chase the tail the long way round, the food may be easier to get later
+    let chase = DIRECTIONS
+        .iter()
+        .filter_map(|d| after_move(game, *d).map(|g| (*d, g)))
+        .filter_map(|(d, g)| distance_to_tail(&g).map(|distance| (d, distance)))
+        .max_by_key(|(_, distance)| *distance);
+    if let Some((direction, _)) = chase {
+        return Some(direction);
+    }
+
+    // Trapped, go where there is the most room and hope the tail opens a way
+    DIRECTIONS
+        .iter()
+        .filter_map(|d| after_move(game, *d).map(|g| (*d, Paths::search(&g).reachable())))
+        .max_by_key(|(_, room)| *room)
+        .map(|(d, _)| d)
+}
+
+// The game after moving one step that way, None if that is a reverse or loses the game
+fn after_move<const W: usize, const H: usize>(game: &Game<W, H>, direction: Direction) -> Option<Game<W, H>> {
+    let heading = game.snake().direction;
+    if direction != heading && turn_towards(heading, direction) == Turn::None {
+        return None;
+    }
+    let mut next = game.clone();
+    next.step(turn_towards(heading, direction));
+    (next.status != GameStatus::Lost).then_some(next)
+}
+
+fn eat_along_shortest_path<const W: usize, const H: usize>(game: &Game<W, H>) -> Game<W, H> {
+    let mut fed = game.clone();
+    for _ in 0..W * H {
+        let Some(direction) = Paths::search(&fed).first_step(fed.food()) else {
+            break;
+        };
+        let score = fed.score;
+        fed.step(turn_towards(fed.snake().direction, direction));
+        if fed.status != GameStatus::Ongoing || fed.score > score {
+            break;
+        }
+    }
+    fed
+}
+
+fn distance_to_tail<const W: usize, const H: usize>(game: &Game<W, H>) -> Option<usize> {
+    match game.snake().tail_end() {
+        Some(tail_end) => Paths::search(game).distance(tail_end),
+        // Without a tail there is nothing to get stuck behind
+        None => Some(0),
+    }
+}
+
+fn tail_reachable<const W: usize, const H: usize>(game: &Game<W, H>) -> bool {
+    distance_to_tail(game).is_some()
+}
+
+fn neighbour<const W: usize, const H: usize>(game: &Game<W, H>, coords: Coords, direction: Direction) -> Option<Coords> {
+    let (row, col) = match direction {
+        Direction::Up => (-1, 0),
+        Direction::Right => (0, 1),
+        Direction::Down => (1, 0),
+        Direction::Left => (0, -1),
+    };
+    let mut next = Coords::new(coords.row + row, coords.col + col);
+    if next.is_out_of_bounds::<W, H>() {
+        if game.rules().edges != Edges::Wrap {
+            return None;
+        }
+        next = Coords::new(next.row.rem_euclid(H as i8), next.col.rem_euclid(W as i8));
+    }
+    (!game.walls().is_wall(&next)).then_some(next)
+}
+
+// Breadth first search from the head. The snake is an obstacle that clears up as it moves:
+// the end of the tail is free after one step, the cell before it after two and so on.
+struct Paths<const W: usize, const H: usize> {
+    // First step from the head towards the cell and the number of steps to get there
+    cells: [[Option<(Direction, usize)>; W]; H],
+}
+
+impl<const W: usize, const H: usize> Paths<W, H> {
+    fn search(game: &Game<W, H>) -> Self {
+        let snake = game.snake();
+        let mut free_after = [[0; W]; H];
+        for (i, cell) in snake.tail().enumerate() {
+            free_after[cell.row as usize][cell.col as usize] = i + 1;
+        }
+        free_after[snake.head.row as usize][snake.head.col as usize] = snake.tail_len() + 1;
+
+        let mut cells = [[None; W]; H];
+        // Every cell gets queued at most once, so a board sized buffer is enough
+        let mut queue = [[snake.head; W]; H];
+        let (mut read, mut write) = (0, 0);
+
+        for direction in DIRECTIONS {
+            // The snake can not turn around on the spot
+            if direction != snake.direction && turn_towards(snake.direction, direction) == Turn::None {
+                continue;
+            }
+            if let Some(next) = neighbour(game, snake.head, direction)
+                && free_after[next.row as usize][next.col as usize] <= 1
+                && cells[next.row as usize][next.col as usize].is_none()
+            {
+                cells[next.row as usize][next.col as usize] = Some((direction, 1));
+                queue[write / W][write % W] = next;
+                write += 1;
+            }
+        }
+
+        while read < write {
+            let current = queue[read / W][read % W];
+            read += 1;
+            let Some((first, distance)) = cells[current.row as usize][current.col as usize] else {
+                continue;
+            };
+            for direction in DIRECTIONS {
+                if let Some(next) = neighbour(game, current, direction)
+                    && next != snake.head
+                    && free_after[next.row as usize][next.col as usize] <= distance + 1
+                    && cells[next.row as usize][next.col as usize].is_none()
+                {
+                    cells[next.row as usize][next.col as usize] = Some((first, distance + 1));
+                    queue[write / W][write % W] = next;
+                    write += 1;
+                }
+            }
+        }
+        Self { cells }
+    }
+
+    fn first_step(&self, to: Coords) -> Option<Direction> {
+        self.cells[to.row as usize][to.col as usize].map(|(direction, _)| direction)
+    }
+
+    fn distance(&self, to: Coords) -> Option<usize> {
+        self.cells[to.row as usize][to.col as usize].map(|(_, distance)| distance)
+    }
+
+    fn reachable(&self) -> usize {
+        self.cells.iter().flatten().filter(|c| c.is_some()).count()
+    }
+}
+
+// Which way to go from each cell to walk through every cell of the board and end up at the start again.
+// None where there is no such cycle: walls in the level, or an odd by odd board that does not wrap.
+pub fn hamiltonian_cycle<const W: usize, const H: usize>(game: &Game<W, H>) -> Option<[[Direction; W]; H]> {
+    if W < 2 || H < 2 || game.walls().wall_count() > 0 {
+        return None;
+    }
+    let mut cycle = [[Direction::Up; W]; H];
+    if H.is_multiple_of(2) {
+        // Zigzag through the rows from the second column on, then back up the first column
+        for (r, row) in cycle.iter_mut().enumerate() {
+            for (c, direction) in row.iter_mut().enumerate() {
+                *direction = match (c, r % 2) {
+                    (0, _) if r == 0 => Direction::Right,
+                    (0, _) => Direction::Up,
+                    (_, 0) if c == W - 1 => Direction::Down,
+                    (_, 0) => Direction::Right,
+                    (1, _) if r == H - 1 => Direction::Left,
+                    (1, _) => Direction::Down,
+                    _ => Direction::Left,
+                };
+            }
+        }
+    } else if W.is_multiple_of(2) {
+        // Same, turned on its side
+        for (r, row) in cycle.iter_mut().enumerate() {
+            for (c, direction) in row.iter_mut().enumerate() {
+                *direction = match (r, c % 2) {
+                    (0, _) if c == 0 => Direction::Down,
+                    (0, _) => Direction::Left,
+                    (_, 0) if r == H - 1 => Direction::Right,
+                    (_, 0) => Direction::Down,
+                    (1, _) if c == W - 1 => Direction::Up,
+                    (1, _) => Direction::Right,
+                    _ => Direction::Up,
+                };
+            }
+        }
+    } else if game.rules().edges == Edges::Wrap && H.is_multiple_of(W) {
+        // Along each row through the edge, then down. Every row starts one column further left,
+        // after H rows that is back at the start.
+        for (r, row) in cycle.iter_mut().enumerate() {
+            let last = (-(r as isize) - 1).rem_euclid(W as isize) as usize;
+            for (c, direction) in row.iter_mut().enumerate() {
+                *direction = if c == last { Direction::Down } else { Direction::Right };
+            }
+        }
+    } else {
+        return None;
+    }
+    Some(cycle)
+}
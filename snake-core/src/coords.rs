@@ -0,0 +1,33 @@
+
+use super::rng::Prng;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Coords {
+    pub row: i8,
+    pub col: i8,
+}
+
+impl Coords {
+    pub fn new(row: i8, col: i8) -> Self {
+        Self {row, col}
+    }
+
+    // Keeps drawing until it finds a cell that is not excluded, so there has to be a free one
+    pub fn random<const W: usize, const H: usize>(rng: &mut Prng, exclude: impl Fn(&Coords) -> bool) -> Self {
+        let mut coords = Coords {
+            row: ((rng.random_u32() as usize) % H) as i8,
+            col: ((rng.random_u32() as usize) % W) as i8,
+        };
+        while exclude(&coords) {
+            coords = Coords {
+                row: ((rng.random_u32() as usize) % H) as i8,
+                col: ((rng.random_u32() as usize) % W) as i8,
+            }
+        }
+        coords
+    }
+
+    pub fn is_out_of_bounds<const W: usize, const H: usize>(&self) -> bool {
+        (self.row as u8 as usize) >= H || (self.col as u8 as usize) >= W
+    }
+}
@@ -0,0 +1,171 @@
+
+use embedded_storage::nor_flash::NorFlash;
+
+pub const TABLE_LEN: usize = 5;
+
+// Best scores first, empty places are 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HighScores {
+    scores: [u16; TABLE_LEN],
+}
+
+impl HighScores {
+    pub fn scores(&self) -> &[u16; TABLE_LEN] {
+        &self.scores
+    }
+
+    pub fn best(&self) -> u16 {
+        self.scores[0]
+    }
+
+    // Returns the place the score got in the table, 0 is a new record
+    pub fn insert(&mut self, score: u16) -> Option<usize> {
+        let place = self.scores.iter().position(|s| score > *s)?;
+        self.scores.copy_within(place..TABLE_LEN - 1, place + 1);
+        self.scores[place] = score;
+        Some(place)
+    }
+}
+
+// Every save appends a full copy of the table to a flash page instead of rewriting it in place.
+// There are two pages taking turns: when one is full the other one is erased and filled next, so
+// the newest record is never on the page being erased and losing power then keeps the scores.
+// Each page sees one erase per 2 * PAGE / RECORD_LEN saves.
+//
+// Record layout, little endian:
+// magic (2) | save counter (2) | scores (2 * TABLE_LEN) | crc16 (2)
+const RECORD_LEN: usize = 16;
+const MAGIC: u16 = 0x534e;
+const BLANK: u8 = 0xff;
+
+pub struct HighScoreStore<F> {
+    flash: F,
+    // Start of the two pages reserved for the table
+    offset: u32,
+    // The page with the newest record, 0 or 1
+    page: usize,
+    // Slot the next record goes into, a full page means the other page gets erased first
+    next_slot: usize,
+    saves: u16,
+}
+
+impl<F: NorFlash> HighScoreStore<F> {
+    // The two pages from offset on have to be reserved for the table, nothing else may live there
+    pub fn new(flash: F, offset: u32) -> Self {
+        assert!(RECORD_LEN.is_multiple_of(F::WRITE_SIZE), "flash write size does not fit the record");
+        assert!((offset as usize).is_multiple_of(F::ERASE_SIZE), "high score page is not aligned");
+        Self {
+            flash,
+            offset,
+            page: 0,
+            // Unknown until loaded, so the first save without a load starts on a fresh page
+            next_slot: Self::slots(),
+            saves: 0,
+        }
+    }
+
+    pub fn free(self) -> F {
+        self.flash
+    }
+
+    fn slots() -> usize {
+        F::ERASE_SIZE / RECORD_LEN
+    }
+
+    // A blank page gives an empty table, records with a bad crc (e.g. power lost while writing) are skipped
+    pub fn load(&mut self) -> Result<HighScores, F::Error> {
+        let mut newest: Option<(u16, HighScores, usize)> = None;
+        let mut blank = [Self::slots(); 2];
+        for (page, first_blank) in blank.iter_mut().enumerate() {
+            for slot in 0..Self::slots() {
+                let mut record = [0; RECORD_LEN];
+                self.flash.read(self.slot_offset(page, slot), &mut record)?;
+                if record.iter().all(|b| *b == BLANK) {
+                    *first_blank = slot;
+                    break;
+                }
+                let Some((saves, scores)) = decode(&record) else { continue };
+                // The counter wraps, newer is a little ahead of older
+                if newest.is_none_or(|(newest_saves, ..)| saves.wrapping_sub(newest_saves) as i16 > 0) {
+                    newest = Some((saves, scores, page));
+                }
+            }
+        }
+        let (saves, table, page) = newest.unwrap_or((0, HighScores::default(), 0));
+        self.saves = saves;
+        self.page = page;
+        self.next_slot = blank[page];
+        Ok(table)
+    }
+
+    pub fn save(&mut self, table: &HighScores) -> Result<(), F::Error> {
+        if self.next_slot >= Self::slots() {
+            let other = 1 - self.page;
+            let start = self.slot_offset(other, 0);
+            self.flash.erase(start, start + F::ERASE_SIZE as u32)?;
+            self.page = other;
+            self.next_slot = 0;
+        }
+        self.saves = self.saves.wrapping_add(1);
+        let record = encode(self.saves, table);
+        self.flash.write(self.slot_offset(self.page, self.next_slot), &record)?;
+        self.next_slot += 1;
+        Ok(())
+    }
+
+    // Load, insert and only write when the score made it into the table
+    pub fn submit(&mut self, score: u16) -> Result<(HighScores, Option<usize>), F::Error> {
+        let mut table = self.load()?;
+        let place = table.insert(score);
+        if place.is_some() {
+            self.save(&table)?;
+        }
+        Ok((table, place))
+    }
+
+    // How often the table was saved since the pages were last found blank
+    pub fn saves(&self) -> u16 {
+        self.saves
+    }
+
+    fn slot_offset(&self, page: usize, slot: usize) -> u32 {
+        self.offset + (page * F::ERASE_SIZE + slot * RECORD_LEN) as u32
+    }
+}
+
+fn encode(saves: u16, table: &HighScores) -> [u8; RECORD_LEN] {
+    let mut record = [0; RECORD_LEN];
+    record[0..2].copy_from_slice(&MAGIC.to_le_bytes());
+    record[2..4].copy_from_slice(&saves.to_le_bytes());
+    for (i, score) in table.scores.iter().enumerate() {
+        record[4 + 2 * i..6 + 2 * i].copy_from_slice(&score.to_le_bytes());
+    }
+    let crc = crc16(&record[..RECORD_LEN - 2]);
+    record[RECORD_LEN - 2..].copy_from_slice(&crc.to_le_bytes());
+    record
+}
+
+fn decode(record: &[u8; RECORD_LEN]) -> Option<(u16, HighScores)> {
+    let crc = u16::from_le_bytes([record[RECORD_LEN - 2], record[RECORD_LEN - 1]]);
+    if u16::from_le_bytes([record[0], record[1]]) != MAGIC || crc16(&record[..RECORD_LEN - 2]) != crc {
+        return None;
+    }
+    let saves = u16::from_le_bytes([record[2], record[3]]);
+    let mut table = HighScores::default();
+    for (i, score) in table.scores.iter_mut().enumerate() {
+        *score = u16::from_le_bytes([record[4 + 2 * i], record[5 + 2 * i]]);
+    }
+    Some((saves, table))
+}
+
+// CRC-16/CCITT-FALSE, small enough to not need a table
+pub(crate) fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xffff;
+    for byte in bytes {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
@@ -0,0 +1,136 @@
+
+// Where turns come from. The game only knows Turn, so buttons, a tilted board or keys over serial
+// all end up behind the same trait and the source can be picked at startup.
+
+use crate::movement::{Direction, Turn};
+
+pub trait TurnSource {
+    // Asked once per step, heading is where the snake goes right now
+    fn next_turn(&mut self, heading: Direction) -> Turn;
+}
+
+impl<T: TurnSource + ?Sized> TurnSource for &mut T {
+    fn next_turn(&mut self, heading: Direction) -> Turn {
+        (**self).next_turn(heading)
+    }
+}
+
+// A button press as the menus see it, both buttons together count as one press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Press {
+    A,
+    B,
+    AB,
+}
+
+impl Press {
+    // A steers left and B right, like on the board
+    pub fn turn(&self) -> Turn {
+        match self {
+            Press::A => Turn::Left,
+            Press::B => Turn::Right,
+            Press::AB => Turn::None,
+        }
+    }
+}
+
+// The turn that points the snake to target, it can not turn around so the opposite direction is None
+pub fn turn_towards(heading: Direction, target: Direction) -> Turn {
+    let index = |d: Direction| match d {
+        Direction::Up => 0,
+        Direction::Right => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+    };
+    match (index(target) + 4 - index(heading)) % 4 {
+        1 => Turn::Right,
+        3 => Turn::Left,
+        _ => Turn::None,
+    }
+}
+
+// Direction the board is tilted in, x to the right and y towards the bottom edge in milli-g.
+// Lying (almost) flat gives None, otherwise the stronger axis wins.
+pub fn tilt_direction(x_mg: i32, y_mg: i32, threshold_mg: i32) -> Option<Direction> {
+    if x_mg.abs() < threshold_mg && y_mg.abs() < threshold_mg {
+        return None;
+    }
+    let direction = if x_mg.abs() >= y_mg.abs() {
+        if x_mg > 0 { Direction::Right } else { Direction::Left }
+    } else if y_mg > 0 {
+        Direction::Down
+    } else {
+        Direction::Up
+    };
+    Some(direction)
+}
+
+// Turns from a terminal: a/d like the simulator, or the left and right arrow keys.
+// Arrows come in as ESC [ D and ESC [ C, so bytes are fed one at a time.
+#[derive(Debug, Default)]
+pub struct KeyDecoder {
+    escape: u8,
+}
+
+impl KeyDecoder {
+    pub const fn new() -> Self {
+        Self { escape: 0 }
+    }
+
+    pub fn feed(&mut self, byte: u8) -> Option<Turn> {
+        match (self.escape, byte) {
+            (_, 0x1b) => {
+                self.escape = 1;
+                None
+            },
+            (1, b'[') => {
+                self.escape = 2;
+                None
+            },
+            (2, b'D') => {
+                self.escape = 0;
+                Some(Turn::Left)
+            },
+            (2, b'C') => {
+                self.escape = 0;
+                Some(Turn::Right)
+            },
+            (_, b'a' | b'A') => {
+                self.escape = 0;
+                Some(Turn::Left)
+            },
+            (_, b'd' | b'D') => {
+                self.escape = 0;
+                Some(Turn::Right)
+            },
+            _ => {
+                self.escape = 0;
+                None
+            },
+        }
+    }
+}
+
+// Plays back a fixed list of turns, one per step, and goes straight once it runs out
+pub struct ScriptedTurns<'a> {
+    turns: &'a [Turn],
+    next: usize,
+}
+
+impl<'a> ScriptedTurns<'a> {
+    pub fn new(turns: &'a [Turn]) -> Self {
+        Self { turns, next: 0 }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.turns.len()
+    }
+}
+
+impl TurnSource for ScriptedTurns<'_> {
+    fn next_turn(&mut self, _heading: Direction) -> Turn {
+        let turn = self.turns.get(self.next).copied().unwrap_or(Turn::None);
+        self.next += 1;
+        turn
+    }
+}
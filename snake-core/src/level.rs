@@ -0,0 +1,91 @@
+
+use crate::coords::Coords;
+
+// Static wall cells the snake can run into, drawn as ASCII art:
+// '#' is a wall, '.' is free, one line per row. Spaces and blank lines are ignored so levels can be indented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Level<const W: usize = 5, const H: usize = 5> {
+    walls: [[bool; W]; H],
+}
+
+impl<const W: usize, const H: usize> Level<W, H> {
+    pub const EMPTY: Self = Self { walls: [[false; W]; H] };
+
+    // Use it in a const, then a typo in the art is a compile error instead of a panic on the board
+    pub const fn parse(art: &str) -> Self {
+        let bytes = art.as_bytes();
+        let mut walls = [[false; W]; H];
+        let mut row = 0;
+        let mut col = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\n' => {
+                    if col != 0 {
+                        assert!(col == W, "level row has the wrong width");
+                        row += 1;
+                        col = 0;
+                    }
+                },
+                b' ' | b'\t' | b'\r' => (),
+                b'#' | b'.' => {
+                    assert!(row < H, "level has too many rows");
+                    assert!(col < W, "level row has the wrong width");
+                    walls[row][col] = bytes[i] == b'#';
+                    col += 1;
+                },
+                _ => panic!("unknown character in level, use '#' for walls and '.' for free cells"),
+            }
+            i += 1;
+        }
+        if col != 0 {
+            assert!(col == W, "level row has the wrong width");
+            row += 1;
+        }
+        assert!(row == H, "level has the wrong number of rows");
+        Self { walls }
+    }
+
+    pub fn is_wall(&self, coords: &Coords) -> bool {
+        !coords.is_out_of_bounds::<W, H>() && self.walls[coords.row as usize][coords.col as usize]
+    }
+
+    pub fn wall_count(&self) -> usize {
+        self.walls.iter().flatten().filter(|w| **w).count()
+    }
+
+    pub fn walls(&self) -> impl Iterator<Item = Coords> + '_ {
+        self.walls.iter().enumerate().flat_map(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, wall)| **wall)
+                .map(move |(c, _)| Coords::new(r as i8, c as i8))
+        })
+    }
+}
+
+// A few levels for the micro:bit display, the middle row stays free for the snake to start in
+pub const LEVELS: [Level; 4] = [
+    Level::EMPTY,
+    Level::parse("
+        #...#
+        .....
+        .....
+        .....
+        #...#
+    "),
+    Level::parse("
+        .....
+        .#.#.
+        .....
+        .#.#.
+        .....
+    "),
+    Level::parse("
+        #.#.#
+        .....
+        .....
+        .....
+        #.#.#
+    "),
+];
@@ -0,0 +1,286 @@
+#![no_std]
+
+// The snake game logic, free of any HAL so it runs on the board and on the host alike
+
+use crate::{coords::Coords, level::Level, movement::{Direction, StepOutcome, Turn}, rules::{Edges, GameRules}};
+
+pub mod coords;
+pub mod rng;
+pub mod snake;
+pub mod movement;
+pub mod rules;
+pub mod replay;
+pub mod level;
+pub mod highscore;
+pub mod sound;
+pub mod input;
+pub mod turn_queue;
+pub mod autopilot;
+pub mod app;
+pub mod versus;
+pub mod speed;
+pub mod snapshot;
+
+
+// W columns by H rows, the micro:bit display is the default
+#[derive(Clone)]
+pub struct Game<const W: usize = 5, const H: usize = 5> {
+    pub status: movement::GameStatus,
+    rules: GameRules,
+    levels: &'static [Level<W, H>],
+    level: usize,
+    // Food eaten since the current level started
+    level_food: u8,
+    rng: rng::Prng,
+    // Where the head goes at the start, after a reset and on every new level
+    start: Coords,
+    snake: snake::Snake<W, H>,
+    food_coords: coords::Coords,
+    // Cells the snake still has to grow from food it already ate
+    pending_growth: u8,
+    speed: u8,
+    // Steps taken since the game started, for speed curves that go by time
+    steps: u32,
+    pub score: u16,
+}
+
+impl<const W: usize, const H: usize> Game<W, H> {
+    pub fn new(seed: u32, rules: GameRules) -> Self {
+        Self::with_levels(seed, rules, &[])
+    }
+
+    // Starts on the first level, see GameRules::food_per_level for moving on
+    pub fn with_levels(seed: u32, rules: GameRules, levels: &'static [Level<W, H>]) -> Self {
+        let mut rng = rng::Prng::new(seed);
+        let start = snake::Snake::<W, H>::centre();
+        let snake = snake::Snake::make_snake_at(start, rules.start_length, rules.start_direction, rules.edges);
+        let walls = levels.first();
+        let food_coords = coords::Coords::random::<W, H>(&mut rng, |c| {
+            snake.contains(c) || walls.is_some_and(|l| l.is_wall(c))
+        });
+        Self { 
+            status: movement::GameStatus::Ongoing, 
+            rules,
+            levels,
+            level: 0,
+            level_food: 0,
+            rng, 
+            start,
+            snake, 
+            food_coords, 
+            pending_growth: 0,
+            speed: 1, 
+            steps: 0,
+            score: 0 
+        }
+    }
+
+    // Starts with the head somewhere else than the middle, e.g. for two snakes on one board
+    pub fn with_start(seed: u32, rules: GameRules, head: Coords) -> Self {
+        let mut game = Self::new(seed, rules);
+        game.start = head;
+        game.snake = snake::Snake::make_snake_at(head, rules.start_length, rules.start_direction, rules.edges);
+        game.place_food();
+        game
+    }
+
+    pub fn reset(&mut self) {
+        self.level = 0;
+        self.level_food = 0;
+        self.snake = snake::Snake::make_snake_at(self.start, self.rules.start_length, self.rules.start_direction, self.rules.edges);
+        self.place_food();
+        self.pending_growth = 0;
+        self.speed = 1;
+        self.steps = 0;
+        self.status = movement::GameStatus::Ongoing;
+        self.score = 0;
+    }
+
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    // Index into the levels the game was made with
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    pub fn walls(&self) -> &Level<W, H> {
+        self.levels.get(self.level).unwrap_or(&Level::EMPTY)
+    }
+
+    pub fn snake(&self) -> &snake::Snake<W, H> {
+        &self.snake
+    }
+
+    pub fn food(&self) -> Coords {
+        self.food_coords
+    }
+
+    // Override the random food placement, e.g. when the food position comes from somewhere else
+    pub fn set_food(&mut self, coords: Coords) {
+        self.food_coords = coords;
+    }
+
+    pub fn speed(&self) -> u8 {
+        self.speed
+    }
+
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    fn place_food(&mut self) -> Coords {
+        let snake = &self.snake;
+        let walls = self.levels.get(self.level).unwrap_or(&Level::EMPTY);
+        let coords = coords::Coords::random::<W, H>(&mut self.rng, |c| snake.contains(c) || walls.is_wall(c));
+        self.food_coords = coords;
+        coords
+    }
+
+    fn wraparound(&self, coords: Coords) -> Coords {
+        if coords.row < 0 {
+            Coords { row: H as i8 - 1, ..coords }
+        } else if coords.row as usize >= H {
+            Coords { row: 0, ..coords }
+        } else if coords.col < 0 {
+            Coords { col: W as i8 - 1, ..coords }
+        } else {
+            Coords { col: 0, ..coords }
+        }
+    }
+
+    fn get_next_move(&self) -> Coords {
+        let head = self.snake.head;
+        let next_move = match self.snake.direction {
+            Direction::Up => Coords {
+                row: head.row - 1,
+                col: head.col
+            },
+            Direction::Down => Coords {
+                row: head.row + 1,
+                col: head.col
+            },
+            Direction::Left => Coords {
+                row: head.row,
+                col: head.col - 1
+            },
+            Direction::Right => Coords {
+                row: head.row,
+                col: head.col + 1
+            }
+        };
+        if next_move.is_out_of_bounds::<W, H>() && self.rules.edges == Edges::Wrap {
+            self.wraparound(next_move)
+        } else {
+            next_move
+        }
+    }
+
+    fn get_step_outcome(&self) -> StepOutcome {
+        let next_move = self.get_next_move();
+        if next_move.is_out_of_bounds::<W, H>() || self.walls().is_wall(&next_move) {
+            StepOutcome::Collision
+        } else if self.snake.contains(&next_move) {
+            // The end of the tail only moves out of the way if the snake is not growing
+            if self.pending_growth > 0 || self.snake.tail_end() != Some(next_move) {
+                StepOutcome::Collision
+            } else {
+                StepOutcome::Move(next_move)
+            }
+        } else if next_move == self.food_coords {
+            let length = self.snake.tail_len() + 1 + self.pending_growth as usize;
+            let win_length = (self.rules.win_length as usize).min(W * H - self.walls().wall_count());
+            if length + self.rules.growth_per_food.max(1) as usize >= win_length {
+                StepOutcome::Full
+            } else {
+                StepOutcome::Eat(next_move)
+            }
+        } else {
+            StepOutcome::Move(next_move)
+        }
+    }
+
+    fn handle_step_outcome(&mut self, outcome: StepOutcome) {
+        self.status = match outcome {
+            StepOutcome::Collision => movement::GameStatus::Lost,
+            StepOutcome::Full => movement::GameStatus::Won,
+            StepOutcome::Eat(c) => {
+                self.snake.move_snake(c, true);
+                self.pending_growth += self.rules.growth_per_food.max(1) - 1;
+                self.place_food();
+                self.score += 1;
+                if self.rules.speed_up_every > 0 && self.score.is_multiple_of(self.rules.speed_up_every as u16) {
+                    self.speed = self.speed.saturating_add(1);
+                }
+                self.level_food += 1;
+                if self.rules.food_per_level > 0
+                    && self.level_food >= self.rules.food_per_level
+                    && self.level + 1 < self.levels.len()
+                {
+                    self.next_level();
+                }
+                movement::GameStatus::Ongoing
+            },
+            StepOutcome::Move(c) => {
+                let extend = self.pending_growth > 0;
+                if extend {
+                    self.pending_growth -= 1;
+                }
+                self.snake.move_snake(c, extend);
+                movement::GameStatus::Ongoing
+            }
+        }
+    }
+
+    // The snake starts over where it started the game, score and speed carry on
+    fn next_level(&mut self) {
+        self.level += 1;
+        self.level_food = 0;
+        self.snake = snake::Snake::make_snake_at(self.start, self.rules.start_length, self.rules.start_direction, self.rules.edges);
+        self.pending_growth = 0;
+        self.place_food();
+    }
+
+    pub fn step(&mut self, turn: Turn) {
+        if self.status != movement::GameStatus::Ongoing {
+            return;
+        }
+        self.steps += 1;
+        self.snake.turn(turn);
+        let outcome = self.get_step_outcome();
+        self.handle_step_outcome(outcome);
+    }
+
+    // With the classic curve, see SpeedCurve for the others
+    pub fn step_len_ms(&self) -> u32 {
+        speed::SpeedCurve::CLASSIC.step_len_ms(self)
+    }
+    
+    pub fn game_matrix(&self, head_brightness: u8, tail_brightness: u8, food_brightness: u8, wall_brightness: u8) -> [[u8; W]; H] {
+        let mut values = [[0; W]; H];
+        for w in self.walls().walls() {
+            values[w.row as usize][w.col as usize] = wall_brightness;
+        }
+        values[self.snake.head.row as usize][self.snake.head.col as usize] = head_brightness;
+        for t in self.snake.tail() {
+            values[t.row as usize][t.col as usize] = tail_brightness;
+        }
+        values[self.food_coords.row as usize][self.food_coords.col as usize] = food_brightness;
+        values
+    }
+
+    pub fn score_matrix(& self) -> [[u8; W]; H] {
+        let mut values = [[0; W]; H];
+        let full_rows = (self.score as usize) / W;
+        for row in values.iter_mut().take(full_rows) {
+            *row = [1; W];
+        }
+        if let Some(row) = values.get_mut(full_rows) {
+            for value in row.iter_mut().take((self.score as usize) % W) {
+                *value = 1;
+            }
+        }
+        values
+    }
+}
@@ -1,7 +1,7 @@
 
-use crate::game::coords::Coords;
+use crate::coords::Coords;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     Up,
     Right,
@@ -9,20 +9,21 @@ pub enum Direction {
     Left
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Turn {
     Left,
     Right,
     None
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameStatus {
     Won,
     Lost,
     Ongoing,
 }
 
+#[derive(Debug)]
 pub enum StepOutcome {
     Full,
     Collision,
@@ -0,0 +1,120 @@
+
+use core::fmt;
+use heapless::Vec;
+
+use crate::{Game, level::Level, movement::Turn, rules::GameRules};
+
+// Everything needed to play a game again step by step: the seed for the food and every turn taken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayLog<const N: usize> {
+    seed: u32,
+    turns: Vec<Turn, N>,
+}
+
+impl<const N: usize> ReplayLog<N> {
+    pub fn new(seed: u32) -> Self {
+        Self { seed, turns: Vec::new() }
+    }
+
+    // Start logging a new game
+    pub fn reset(&mut self, seed: u32) {
+        self.seed = seed;
+        self.turns.clear();
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    // Hands the turn back when the log is full
+    pub fn record(&mut self, turn: Turn) -> Result<(), Turn> {
+        self.turns.push(turn)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.turns.is_full()
+    }
+
+    // Read back what the Display impl wrote, e.g. a line copied from the RTT console
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.split_whitespace();
+        if parts.next()? != "seed" {
+            return None;
+        }
+        let seed = u32::from_str_radix(parts.next()?, 16).ok()?;
+        if parts.next()? != "turns" {
+            return None;
+        }
+        let mut log = Self::new(seed);
+        for part in parts {
+            for c in part.chars() {
+                log.record(turn_from_char(c)?).ok()?;
+            }
+        }
+        Some(log)
+    }
+
+    // Needs the same rules and levels the game was played with, &[] for none
+    pub fn replay<const W: usize, const H: usize>(&self, rules: GameRules, levels: &'static [Level<W, H>]) -> Replay<'_, W, H> {
+        Replay {
+            game: Game::with_levels(self.seed, rules, levels),
+            turns: self.turns.iter(),
+        }
+    }
+}
+
+// One line, so it can be dumped with rprintln! or written to the UART as is
+impl<const N: usize> fmt::Display for ReplayLog<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "seed {:08x} turns ", self.seed)?;
+        for turn in &self.turns {
+            write!(f, "{}", turn_to_char(*turn))?;
+        }
+        Ok(())
+    }
+}
+
+// Feeds a log back into a fresh game, one step at a time
+pub struct Replay<'a, const W: usize = 5, const H: usize = 5> {
+    game: Game<W, H>,
+    turns: core::slice::Iter<'a, Turn>,
+}
+
+impl<const W: usize, const H: usize> Replay<'_, W, H> {
+    pub fn game(&self) -> &Game<W, H> {
+        &self.game
+    }
+
+    // Returns the turn that was applied, None once the log is used up
+    pub fn step(&mut self) -> Option<Turn> {
+        let turn = *self.turns.next()?;
+        self.game.step(turn);
+        Some(turn)
+    }
+
+    pub fn finish(mut self) -> Game<W, H> {
+        while self.step().is_some() {}
+        self.game
+    }
+}
+
+fn turn_to_char(turn: Turn) -> char {
+    match turn {
+        Turn::Left => 'L',
+        Turn::Right => 'R',
+        Turn::None => '.',
+    }
+}
+
+fn turn_from_char(c: char) -> Option<Turn> {
+    match c {
+        'L' => Some(Turn::Left),
+        'R' => Some(Turn::Right),
+        '.' => Some(Turn::None),
+        _ => None,
+    }
+}
@@ -1,20 +1,21 @@
-use microbit::hal::Rng;
 
+#[derive(Debug, Clone)]
 pub struct Prng {
     value: u32,
 }
 
 impl Prng {
-    // Take a reference to the periphereal, so it still can be used by other parts
-    pub fn seeded(hardware_rng: &mut Rng) -> Self {
-        Self::new(hardware_rng.random_u32())
-    }
-
+    // The seed comes from the caller (e.g. the hardware RNG), so this stays free of any HAL
     pub fn new(mut seed: u32) -> Self {
         if seed == 0 {seed = 1};
         Self { value: seed }
     }
 
+    // Where the sequence is right now, Prng::new with it carries on from here
+    pub fn state(&self) -> u32 {
+        self.value
+    }
+
     pub fn random_u32(&mut self) -> u32 {
         self.value = Self::xorshift32(self.value);
         self.value
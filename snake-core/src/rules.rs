@@ -0,0 +1,56 @@
+
+use crate::movement::Direction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    // Leaving the board on one side comes back in on the other
+    Wrap,
+    // Leaving the board is a collision
+    Walls,
+}
+
+// Everything that makes one game mode different from another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameRules {
+    pub edges: Edges,
+    // How many cells the snake grows for each food, spread over the following steps
+    pub growth_per_food: u8,
+    // The game is won once eating brings the snake to this many cells, capped at the size of the board
+    pub win_length: u16,
+    // Head included, laid out behind the head from the middle of the board, at most one row or column long.
+    // With walls it stops at the edge behind the head instead of wrapping through it.
+    pub start_length: u8,
+    pub start_direction: Direction,
+    // Speed goes up by one every this many points, 0 keeps the speed constant
+    pub speed_up_every: u8,
+    // Move on to the next level after this much food, 0 stays on the first level
+    pub food_per_level: u8,
+}
+
+impl GameRules {
+    pub const CLASSIC: Self = Self {
+        edges: Edges::Wrap,
+        growth_per_food: 1,
+        win_length: u16::MAX,
+        start_length: 2,
+        start_direction: Direction::Right,
+        speed_up_every: 5,
+        food_per_level: 0,
+    };
+
+    pub const WALLS: Self = Self {
+        edges: Edges::Walls,
+        ..Self::CLASSIC
+    };
+
+    pub const LEVELS: Self = Self {
+        food_per_level: 5,
+        ..Self::CLASSIC
+    };
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
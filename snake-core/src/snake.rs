@@ -0,0 +1,186 @@
+use crate::{coords::Coords, movement::{Direction, Turn}, rules::Edges};
+
+#[derive(Debug, Clone)]
+pub struct Snake<const W: usize = 5, const H: usize = 5> {
+    pub head: Coords,
+    pub direction: Direction,
+    // Every cell of the tail points to the next cell towards the head,
+    // so the whole body fits in one slot per cell of the board
+    body: [[Option<Coords>; W]; H],
+    // Same as the head while the snake has no tail
+    tail_end: Coords,
+    tail_len: usize,
+}
+
+impl<const W: usize, const H: usize> Snake<W, H> {
+    // Where make_snake puts the head
+    pub fn centre() -> Coords {
+        Coords::new((H / 2) as i8, (W / 2) as i8)
+    }
+
+    pub fn make_snake(length: u8, direction: Direction) -> Self {
+        Self::make_snake_at(Self::centre(), length, direction, Edges::Wrap)
+    }
+
+    pub fn make_snake_at(head: Coords, length: u8, direction: Direction, edges: Edges) -> Self {
+        // Walk backwards from the head, wrapping around the edges if needed
+        let (row_step, col_step, max_length) = match direction {
+            Direction::Up => (1, 0, H),
+            Direction::Down => (-1, 0, H),
+            Direction::Left => (0, 1, W),
+            Direction::Right => (0, -1, W),
+        };
+        // With walls the tail can not pass through one, so it only gets the cells up to the edge
+        let max_length = match (edges, direction) {
+            (Edges::Wrap, _) => max_length,
+            (Edges::Walls, Direction::Up) => H - head.row as usize,
+            (Edges::Walls, Direction::Down) => head.row as usize + 1,
+            (Edges::Walls, Direction::Left) => W - head.col as usize,
+            (Edges::Walls, Direction::Right) => head.col as usize + 1,
+        };
+        let length = (length as usize).clamp(1, max_length);
+
+        let mut body = [[None; W]; H];
+        let mut next = head;
+        for _ in 1..length {
+            let cell = Coords::new(
+                (next.row + row_step).rem_euclid(H as i8),
+                (next.col + col_step).rem_euclid(W as i8),
+            );
+            body[cell.row as usize][cell.col as usize] = Some(next);
+            next = cell;
+        }
+        Self { 
+            head, 
+            direction,
+            body,
+            tail_end: next,
+            tail_len: length - 1,
+        }
+    }
+
+    // Walks back from the head one direction per tail cell, None if the tail runs into itself
+    pub(crate) fn from_back(head: Coords, direction: Direction, back: impl Iterator<Item = Direction>) -> Option<Self> {
+        let mut body = [[None; W]; H];
+        let mut next = head;
+        let mut tail_len = 0;
+        for step in back {
+            let cell = neighbour::<W, H>(next, step);
+            if cell == head || body[cell.row as usize][cell.col as usize].is_some() {
+                return None;
+            }
+            body[cell.row as usize][cell.col as usize] = Some(next);
+            next = cell;
+            tail_len += 1;
+        }
+        Some(Self { head, direction, body, tail_end: next, tail_len })
+    }
+
+    pub fn move_snake(&mut self, coords: Coords, extend: bool) {
+        // Place current head inside the tail
+        self.body[self.head.row as usize][self.head.col as usize] = Some(coords);
+        // Free the back of the tail before taking the new cell, the head may move onto that exact cell
+        if extend {
+            self.tail_len += 1;
+        } else {
+            let back = self.tail_end;
+            self.tail_end = self.body[back.row as usize][back.col as usize].take().unwrap();
+        }
+        // Set head to new position
+        self.head = coords;
+    }
+
+    // Whether the snake, head or tail, is on this cell
+    pub fn contains(&self, coords: &Coords) -> bool {
+        if coords.is_out_of_bounds::<W, H>() {
+            return false;
+        }
+        *coords == self.head || self.body[coords.row as usize][coords.col as usize].is_some()
+    }
+
+    pub fn tail_len(&self) -> usize {
+        self.tail_len
+    }
+
+    // The cell that frees up on the next move, None without a tail
+    pub fn tail_end(&self) -> Option<Coords> {
+        if self.tail_len == 0 {
+            None
+        } else {
+            Some(self.tail_end)
+        }
+    }
+
+    // From the end of the tail up to the cell behind the head
+    pub fn tail(&self) -> Tail<'_, W, H> {
+        Tail {
+            snake: self,
+            next: self.tail_end,
+            left: self.tail_len,
+        }
+    }
+
+    pub fn turn_right(&mut self) {
+        self.direction = match self.direction {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(&mut self) {
+        self.direction = match self.direction {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn(&mut self, direction: Turn) {
+        match direction {
+            Turn::Right => self.turn_right(),
+            Turn::Left => self.turn_left(),
+            Turn::None => (),
+        }
+    }
+}
+
+// The cell next to coords, wrapping around the edges
+pub(crate) fn neighbour<const W: usize, const H: usize>(coords: Coords, direction: Direction) -> Coords {
+    let (row, col) = match direction {
+        Direction::Up => (coords.row - 1, coords.col),
+        Direction::Down => (coords.row + 1, coords.col),
+        Direction::Left => (coords.row, coords.col - 1),
+        Direction::Right => (coords.row, coords.col + 1),
+    };
+    Coords::new(row.rem_euclid(H as i8), col.rem_euclid(W as i8))
+}
+
+// Which way to go from one cell to the next one, None if they are not next to each other
+pub(crate) fn direction_between<const W: usize, const H: usize>(from: Coords, to: Coords) -> Option<Direction> {
+    [Direction::Up, Direction::Right, Direction::Down, Direction::Left]
+        .into_iter()
+        .find(|d| neighbour::<W, H>(from, *d) == to)
+}
+
+pub struct Tail<'a, const W: usize, const H: usize> {
+    snake: &'a Snake<W, H>,
+    next: Coords,
+    left: usize,
+}
+
+impl<const W: usize, const H: usize> Iterator for Tail<'_, W, H> {
+    type Item = Coords;
+
+    fn next(&mut self) -> Option<Coords> {
+        if self.left == 0 {
+            return None;
+        }
+        let current = self.next;
+        self.next = self.snake.body[current.row as usize][current.col as usize]?;
+        self.left -= 1;
+        Some(current)
+    }
+}
@@ -0,0 +1,195 @@
+
+// A game squeezed into a few bytes, e.g. to keep the last state around for a crash dump.
+// Everything needed to carry on playing is in there, except the rules and levels the game was made with.
+//
+// Layout, numbers little endian:
+//  0      MAGIC
+//  1      VERSION
+//  2..4   width, height
+//  4..6   head row, col
+//  6      direction | status << 2
+//  7..9   food row, col
+//  9..11  score
+//  11     speed
+//  12     level
+//  13     food eaten on this level
+//  14     growth still to come
+//  15..19 rng state
+//  19..23 steps
+//  23..25 tail length
+//  25..   the tail as directions walking back from the head, 2 bits each, 4 to a byte
+//  last   crc8 over everything before it
+
+use serial_frame::crc8;
+
+use crate::{
+    Game,
+    coords::Coords,
+    level::Level,
+    movement::{Direction, GameStatus},
+    rng::Prng,
+    rules::GameRules,
+    snake::{Snake, direction_between},
+};
+
+pub const MAGIC: u8 = 0x5c;
+pub const VERSION: u8 = 1;
+const HEADER_LEN: usize = 25;
+
+// Enough for any game on a board this size, the tail is at most one cell short of the board
+pub const fn snapshot_len(width: usize, height: usize) -> usize {
+    HEADER_LEN + (width * height).div_ceil(4) + 1
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotError {
+    // The buffer to write to or read from is too short
+    TooShort,
+    BadMagic,
+    UnknownVersion(u8),
+    BadChecksum,
+    // Taken on another board size
+    WrongSize { width: u8, height: u8 },
+    // Checksum is fine but the game in it is not, e.g. a tail that runs into itself
+    Invalid,
+}
+
+fn direction_bits(direction: Direction) -> u8 {
+    match direction {
+        Direction::Up => 0,
+        Direction::Right => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+    }
+}
+
+fn bits_direction(bits: u8) -> Direction {
+    match bits & 0b11 {
+        0 => Direction::Up,
+        1 => Direction::Right,
+        2 => Direction::Down,
+        _ => Direction::Left,
+    }
+}
+
+fn status_bits(status: GameStatus) -> u8 {
+    match status {
+        GameStatus::Ongoing => 0,
+        GameStatus::Won => 1,
+        GameStatus::Lost => 2,
+    }
+}
+
+fn bits_status(bits: u8) -> Option<GameStatus> {
+    match bits {
+        0 => Some(GameStatus::Ongoing),
+        1 => Some(GameStatus::Won),
+        2 => Some(GameStatus::Lost),
+        _ => None,
+    }
+}
+
+// The width and height a snapshot was taken on, without decoding the rest
+pub fn board_size(bytes: &[u8]) -> Option<(usize, usize)> {
+    match bytes {
+        [MAGIC, _, width, height, ..] => Some((*width as usize, *height as usize)),
+        _ => None,
+    }
+}
+
+impl<const W: usize, const H: usize> Game<W, H> {
+    // Writes the snapshot to the start of buf and returns how many bytes it took
+    pub fn snapshot(&self, buf: &mut [u8]) -> Result<usize, SnapshotError> {
+        let tail_len = self.snake.tail_len();
+        let len = HEADER_LEN + tail_len.div_ceil(4) + 1;
+        if buf.len() < len {
+            return Err(SnapshotError::TooShort);
+        }
+        let head = self.snake.head;
+        buf[0] = MAGIC;
+        buf[1] = VERSION;
+        buf[2] = W as u8;
+        buf[3] = H as u8;
+        buf[4] = head.row as u8;
+        buf[5] = head.col as u8;
+        buf[6] = direction_bits(self.snake.direction) | status_bits(self.status) << 2;
+        buf[7] = self.food_coords.row as u8;
+        buf[8] = self.food_coords.col as u8;
+        buf[9..11].copy_from_slice(&self.score.to_le_bytes());
+        buf[11] = self.speed;
+        buf[12] = self.level as u8;
+        buf[13] = self.level_food;
+        buf[14] = self.pending_growth;
+        buf[15..19].copy_from_slice(&self.rng.state().to_le_bytes());
+        buf[19..23].copy_from_slice(&self.steps.to_le_bytes());
+        buf[23..25].copy_from_slice(&(tail_len as u16).to_le_bytes());
+
+        // The tail iterator goes from the end towards the head, the snapshot the other way round
+        buf[HEADER_LEN..len - 1].fill(0);
+        let cells = self.snake.tail().zip(self.snake.tail().skip(1).chain(core::iter::once(head)));
+        for (k, (cell, towards_head)) in cells.enumerate() {
+            let back = direction_between::<W, H>(towards_head, cell).ok_or(SnapshotError::Invalid)?;
+            let index = tail_len - 1 - k;
+            buf[HEADER_LEN + index / 4] |= direction_bits(back) << (2 * (index % 4));
+        }
+        buf[len - 1] = crc8(&buf[..len - 1]);
+        Ok(len)
+    }
+
+    // The rules and levels have to be the ones the snapshot was taken with
+    pub fn from_snapshot(bytes: &[u8], rules: GameRules, levels: &'static [Level<W, H>]) -> Result<Self, SnapshotError> {
+        if bytes.len() < HEADER_LEN + 1 {
+            return Err(SnapshotError::TooShort);
+        }
+        if bytes[0] != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+        if bytes[1] != VERSION {
+            return Err(SnapshotError::UnknownVersion(bytes[1]));
+        }
+        if (bytes[2] as usize, bytes[3] as usize) != (W, H) {
+            return Err(SnapshotError::WrongSize { width: bytes[2], height: bytes[3] });
+        }
+        let tail_len = u16::from_le_bytes([bytes[23], bytes[24]]) as usize;
+        let len = HEADER_LEN + tail_len.div_ceil(4) + 1;
+        if bytes.len() < len {
+            return Err(SnapshotError::TooShort);
+        }
+        if crc8(&bytes[..len - 1]) != bytes[len - 1] {
+            return Err(SnapshotError::BadChecksum);
+        }
+
+        let head = Coords::new(bytes[4] as i8, bytes[5] as i8);
+        let food_coords = Coords::new(bytes[7] as i8, bytes[8] as i8);
+        let level = bytes[12] as usize;
+        let rng_state = u32::from_le_bytes([bytes[15], bytes[16], bytes[17], bytes[18]]);
+        let valid = !head.is_out_of_bounds::<W, H>()
+            && !food_coords.is_out_of_bounds::<W, H>()
+            && (level < levels.len() || level == 0)
+            && tail_len < W * H
+            && rng_state != 0;
+        if !valid {
+            return Err(SnapshotError::Invalid);
+        }
+        let status = bits_status(bytes[6] >> 2).ok_or(SnapshotError::Invalid)?;
+        let back = (0..tail_len).map(|index| bits_direction(bytes[HEADER_LEN + index / 4] >> (2 * (index % 4))));
+        let snake = Snake::from_back(head, bits_direction(bytes[6]), back).ok_or(SnapshotError::Invalid)?;
+
+        Ok(Self {
+            status,
+            rules,
+            levels,
+            level,
+            level_food: bytes[13],
+            rng: Prng::new(rng_state),
+            // Not kept in the snapshot, games from with_start are not dumped
+            start: Snake::<W, H>::centre(),
+            snake,
+            food_coords,
+            pending_growth: bytes[14],
+            speed: bytes[11],
+            steps: u32::from_le_bytes([bytes[19], bytes[20], bytes[21], bytes[22]]),
+            score: u16::from_le_bytes([bytes[9], bytes[10]]),
+        })
+    }
+}
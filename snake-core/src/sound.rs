@@ -0,0 +1,150 @@
+
+// Short sound effects for the game and the timing to play them on a plain square wave speaker.
+// The player only says how long to wait until the next edge, so any timer interrupt can drive it.
+
+use crate::{Game, movement::GameStatus};
+
+// A tone gliding from one frequency to another, from == to is a steady tone and 0 Hz is a rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Note {
+    pub from_hz: u32,
+    pub to_hz: u32,
+    pub ms: u32,
+}
+
+impl Note {
+    pub const fn tone(hz: u32, ms: u32) -> Self {
+        Self { from_hz: hz, to_hz: hz, ms }
+    }
+
+    pub const fn sweep(from_hz: u32, to_hz: u32, ms: u32) -> Self {
+        Self { from_hz, to_hz, ms }
+    }
+
+    pub const fn rest(ms: u32) -> Self {
+        Self { from_hz: 0, to_hz: 0, ms }
+    }
+
+    fn is_rest(&self) -> bool {
+        self.from_hz == 0 && self.to_hz == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEffect {
+    Eat,
+    Death,
+    Win,
+    SpeedUp,
+}
+
+const EAT: [Note; 1] = [Note::sweep(880, 1760, 60)];
+const DEATH: [Note; 1] = [Note::sweep(800, 100, 600)];
+const WIN: [Note; 7] = [
+    Note::tone(523, 120),
+    Note::rest(20),
+    Note::tone(659, 120),
+    Note::rest(20),
+    Note::tone(784, 120),
+    Note::rest(20),
+    Note::tone(1047, 300),
+];
+const SPEED_UP: [Note; 3] = [Note::tone(1320, 40), Note::rest(30), Note::tone(1320, 40)];
+
+impl SoundEffect {
+    pub fn notes(&self) -> &'static [Note] {
+        match self {
+            SoundEffect::Eat => &EAT,
+            SoundEffect::Death => &DEATH,
+            SoundEffect::Win => &WIN,
+            SoundEffect::SpeedUp => &SPEED_UP,
+        }
+    }
+
+    // Which effect fits what the last Game::step did, pass in score and speed from before the step
+    pub fn after_step<const W: usize, const H: usize>(score_before: u16, speed_before: u8, game: &Game<W, H>) -> Option<Self> {
+        match game.status {
+            GameStatus::Won => Some(SoundEffect::Win),
+            GameStatus::Lost => Some(SoundEffect::Death),
+            GameStatus::Ongoing if game.speed() > speed_before => Some(SoundEffect::SpeedUp),
+            GameStatus::Ongoing if game.score > score_before => Some(SoundEffect::Eat),
+            GameStatus::Ongoing => None,
+        }
+    }
+}
+
+// What to do at the next edge: drive the speaker high or low and wait that long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub high: bool,
+    pub wait_us: u32,
+}
+
+pub struct Player {
+    notes: &'static [Note],
+    note: usize,
+    // Time spent in the current note
+    elapsed_us: u32,
+    high: bool,
+}
+
+impl Player {
+    pub const fn new() -> Self {
+        Self { notes: &[], note: 0, elapsed_us: 0, high: false }
+    }
+
+    // Cuts off whatever was playing
+    pub fn play(&mut self, effect: SoundEffect) {
+        self.play_notes(effect.notes());
+    }
+
+    // Any tune, not just the effects
+    pub fn play_notes(&mut self, notes: &'static [Note]) {
+        self.notes = notes;
+        self.note = 0;
+        self.elapsed_us = 0;
+        self.high = false;
+    }
+
+    pub fn stop(&mut self) {
+        self.notes = &[];
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.note < self.notes.len()
+    }
+
+    // None once the effect is over, the speaker should be left low then
+    pub fn next_edge(&mut self) -> Option<Edge> {
+        // A note of 0 ms has no edges at all
+        while self.notes.get(self.note).is_some_and(|note| note.ms == 0) {
+            self.note += 1;
+        }
+        let note = *self.notes.get(self.note)?;
+        let duration_us = note.ms * 1000;
+
+        let edge = if note.is_rest() {
+            self.high = false;
+            Edge { high: false, wait_us: duration_us - self.elapsed_us }
+        } else {
+            // Frequency moves linearly from start to end of the note
+            let progress = (note.to_hz as i64 - note.from_hz as i64) * self.elapsed_us as i64 / duration_us as i64;
+            let hz = (note.from_hz as i64 + progress).max(1) as u32;
+            self.high = !self.high;
+            Edge { high: self.high, wait_us: (500_000 / hz).min(duration_us - self.elapsed_us).max(1) }
+        };
+
+        self.elapsed_us += edge.wait_us;
+        if self.elapsed_us >= duration_us {
+            self.note += 1;
+            self.elapsed_us = 0;
+        }
+        Some(edge)
+    }
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
+}
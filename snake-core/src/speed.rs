@@ -0,0 +1,79 @@
+
+// How long a step takes as the game goes on. The level starts at 1 and only goes up,
+// every curve gets faster (or stays) with the level and never goes below its minimum.
+
+use crate::Game;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeedCurve {
+    // start_ms at level 1, step_ms less per level
+    Linear { start_ms: u32, step_ms: u32, min_ms: u32 },
+    // Every level takes percent of the one before, so it speeds up quickly at first and then levels off
+    Exponential { start_ms: u32, percent: u32, min_ms: u32 },
+    // One entry per level, the last one holds for all levels after it. Build it with
+    // SpeedCurve::stepped, that checks the table is not empty and never goes up.
+    Stepped(&'static [u32]),
+    // Levels come from surviving instead of eating: one more every so many steps
+    TimeBased { every_steps: u32, curve: &'static SpeedCurve },
+}
+
+impl SpeedCurve {
+    // What Game::step_len_ms has always done
+    pub const CLASSIC: SpeedCurve = SpeedCurve::Linear { start_ms: 1000, step_ms: 200, min_ms: 200 };
+
+    // Panics on a bad table, in a const that is a compile error
+    pub const fn stepped(table: &'static [u32]) -> Self {
+        assert!(!table.is_empty(), "speed table is empty");
+        let mut i = 1;
+        while i < table.len() {
+            assert!(table[i] <= table[i - 1], "speed table slows down");
+            i += 1;
+        }
+        SpeedCurve::Stepped(table)
+    }
+
+    // Level 1 at the start, score based curves follow Game::speed
+    pub fn level<const W: usize, const H: usize>(&self, game: &Game<W, H>) -> u32 {
+        match self {
+            SpeedCurve::TimeBased { every_steps, .. } => 1 + game.steps() / (*every_steps).max(1),
+            _ => game.speed().max(1) as u32,
+        }
+    }
+
+    pub fn step_len_ms<const W: usize, const H: usize>(&self, game: &Game<W, H>) -> u32 {
+        self.step_len_at(self.level(game))
+    }
+
+    pub fn step_len_at(&self, level: u32) -> u32 {
+        let level = level.max(1);
+        match self {
+            SpeedCurve::Linear { start_ms, step_ms, min_ms } => {
+                start_ms.saturating_sub(step_ms.saturating_mul(level - 1)).max(*min_ms)
+            },
+            SpeedCurve::Exponential { start_ms, percent, min_ms } => {
+                let mut ms = *start_ms;
+                for _ in 1..level {
+                    let next = ms.saturating_mul((*percent).min(100)) / 100;
+                    // At the minimum or stuck, e.g. 100 percent or too few ms left to shrink
+                    if ms <= *min_ms || next == ms {
+                        break;
+                    }
+                    ms = next;
+                }
+                ms.max(*min_ms)
+            },
+            SpeedCurve::Stepped(table) => match table.last() {
+                Some(last) => *table.get(level as usize - 1).unwrap_or(last),
+                // Only when the variant was built by hand, play it like the classic game then
+                None => Self::CLASSIC.step_len_at(level),
+            },
+            SpeedCurve::TimeBased { curve, .. } => curve.step_len_at(level),
+        }
+    }
+}
+
+impl Default for SpeedCurve {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
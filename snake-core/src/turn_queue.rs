@@ -0,0 +1,101 @@
+
+// Presses are queued by the interrupt and taken out one per step, so two quick presses inside
+// one step turn twice instead of the second one overwriting the first.
+//
+// What gets dropped on the way out:
+// - Turn::None, it does not steer
+// - presses older than STALE_MS, the player has already forgotten about them
+// - a Left and a Right within CHORD_MS of each other, that is both buttons pressed together
+//   and not a steer. next_press gives those as Press::AB, next_turn drops them. A lone press
+//   younger than CHORD_MS is held back until the other button had its chance.
+// Two turns the same way in a row are kept, that is a U-turn. A Left followed by a Right later on
+// is kept too, that moves the snake over by one line.
+
+use heapless::spsc::Consumer;
+
+use crate::{input::Press, movement::Turn};
+
+pub const CHORD_MS: u32 = 50;
+pub const STALE_MS: u32 = 1500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TurnEvent {
+    pub turn: Turn,
+    // From a free running millisecond clock, it may wrap
+    pub at_ms: u32,
+}
+
+impl TurnEvent {
+    pub fn new(turn: Turn, at_ms: u32) -> Self {
+        Self { turn, at_ms }
+    }
+}
+
+fn is_chord(first: &TurnEvent, second: &TurnEvent) -> bool {
+    let opposite = matches!((first.turn, second.turn), (Turn::Left, Turn::Right) | (Turn::Right, Turn::Left));
+    opposite && second.at_ms.wrapping_sub(first.at_ms) <= CHORD_MS
+}
+
+// The game loop end of the queue, the interrupt holds the Producer
+pub struct TurnReader<'a, const N: usize> {
+    events: Consumer<'a, TurnEvent, N>,
+    // Taken out of the queue already, waiting to see if it turns into a chord
+    held: Option<TurnEvent>,
+}
+
+impl<'a, const N: usize> TurnReader<'a, N> {
+    pub fn new(events: Consumer<'a, TurnEvent, N>) -> Self {
+        Self { events, held: None }
+    }
+
+    // At most one press per call, the rest stays queued for the next steps
+    pub fn next_press(&mut self, now_ms: u32) -> Option<Press> {
+        while let Some(event) = self.held.take().or_else(|| self.events.dequeue()) {
+            // Negative when the interrupt stamped it after now_ms was read
+            let age = now_ms.wrapping_sub(event.at_ms) as i32;
+            if age > STALE_MS as i32 {
+                continue;
+            }
+            match self.events.peek() {
+                Some(next) if is_chord(&event, next) => {
+                    self.events.dequeue();
+                    return Some(Press::AB);
+                },
+                None if event.turn != Turn::None && age < CHORD_MS as i32 => {
+                    self.held = Some(event);
+                    return None;
+                },
+                _ => (),
+            }
+            match event.turn {
+                Turn::Left => return Some(Press::A),
+                Turn::Right => return Some(Press::B),
+                Turn::None => (),
+            }
+        }
+        None
+    }
+
+    // Like next_press, for steering only
+    pub fn next_turn(&mut self, now_ms: u32) -> Turn {
+        while let Some(press) = self.next_press(now_ms) {
+            if press != Press::AB {
+                return press.turn();
+            }
+        }
+        Turn::None
+    }
+
+    pub fn clear(&mut self) {
+        self.held = None;
+        while self.events.dequeue().is_some() {}
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len() + self.held.is_some() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
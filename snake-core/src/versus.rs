@@ -0,0 +1,437 @@
+
+// Two snakes on one board, one per micro:bit, linked by a serial line.
+//
+// Both boards run the whole Versus game. They start from the same seed and only exchange turns,
+// so as long as both got the same turns for every step both games stay the same. Every step frame
+// carries the food and a checksum of the game as the sender has it, a mismatch is a desync.
+//
+// Lockstep: a board sends its turn for step n over and over until it has the other board's turn for
+// step n, then steps. The other board can only be one step ahead, so each frame also carries the turn
+// of the step before in case the frame for that step got lost.
+
+use serial_frame::FrameDecoder;
+
+use crate::{
+    Game,
+    coords::Coords,
+    movement::{Direction, GameStatus, Turn},
+    rng::Prng,
+    rules::{Edges, GameRules},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Player {
+    First,
+    Second,
+}
+
+impl Player {
+    fn index(self) -> usize {
+        match self {
+            Player::First => 0,
+            Player::Second => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersusStatus {
+    Ongoing,
+    Won(Player),
+    // Both crashed on the same step, or the snakes filled the board between them
+    Draw,
+}
+
+pub const VERSUS_RULES: GameRules = GameRules {
+    edges: Edges::Wrap,
+    growth_per_food: 1,
+    win_length: u16::MAX,
+    start_length: 2,
+    start_direction: Direction::Right,
+    speed_up_every: 5,
+    food_per_level: 0,
+};
+
+// The first snake starts top left going right, the second bottom right going left
+pub struct Versus<const W: usize = 5, const H: usize = 5> {
+    games: [Game<W, H>; 2],
+    rng: Prng,
+    food: Coords,
+    status: VersusStatus,
+    step: u16,
+}
+
+impl<const W: usize, const H: usize> Versus<W, H> {
+    pub fn new(seed: u32) -> Self {
+        let first = Game::with_start(seed, VERSUS_RULES, Coords::new(1, 1));
+        let second_rules = GameRules { start_direction: Direction::Left, ..VERSUS_RULES };
+        let second = Game::with_start(seed, second_rules, Coords::new(H as i8 - 2, W as i8 - 2));
+        let mut versus = Self {
+            games: [first, second],
+            rng: Prng::new(seed),
+            food: Coords::new(0, 0),
+            status: VersusStatus::Ongoing,
+            step: 0,
+        };
+        versus.place_food();
+        versus
+    }
+
+    pub fn game(&self, player: Player) -> &Game<W, H> {
+        &self.games[player.index()]
+    }
+
+    pub fn food(&self) -> Coords {
+        self.food
+    }
+
+    pub fn status(&self) -> VersusStatus {
+        self.status
+    }
+
+    // Steps taken so far
+    pub fn steps(&self) -> u16 {
+        self.step
+    }
+
+    // Both snakes move at once, turns are in player order
+    pub fn step(&mut self, turns: [Turn; 2]) {
+        if self.status != VersusStatus::Ongoing {
+            return;
+        }
+        let scores = self.games.each_ref().map(|g| g.score);
+        for (game, turn) in self.games.iter_mut().zip(turns) {
+            game.step(turn);
+        }
+        self.step = self.step.wrapping_add(1);
+
+        let [first, second] = &self.games;
+        let (head_first, head_second) = (first.snake().head, second.snake().head);
+        let mut lost = [first.status == GameStatus::Lost, second.status == GameStatus::Lost];
+        if head_first == head_second {
+            lost = [true, true];
+        }
+        lost[0] |= second.snake().contains(&head_first);
+        lost[1] |= first.snake().contains(&head_second);
+
+        self.status = match lost {
+            [true, true] => VersusStatus::Draw,
+            [true, false] => VersusStatus::Won(Player::Second),
+            [false, true] => VersusStatus::Won(Player::First),
+            // Filling up the board on its own also wins
+            [false, false] if first.status == GameStatus::Won => VersusStatus::Won(Player::First),
+            [false, false] if second.status == GameStatus::Won => VersusStatus::Won(Player::Second),
+            [false, false] => VersusStatus::Ongoing,
+        };
+
+        if self.status == VersusStatus::Ongoing && self.games.iter().zip(scores).any(|(g, score)| g.score > score) {
+            self.place_food();
+        }
+    }
+
+    // Each game placed its own food after eating, the shared one has to keep clear of both snakes.
+    // Each game only checks its own snake for a full board, so no room left is caught here.
+    fn place_food(&mut self) {
+        let [first, second] = &self.games;
+        let taken = |c: &Coords| first.snake().contains(c) || second.snake().contains(c);
+        let free = (0..H as i8).flat_map(|row| (0..W as i8).map(move |col| Coords::new(row, col))).filter(|c| !taken(c)).count();
+        if free == 0 {
+            self.status = VersusStatus::Draw;
+            return;
+        }
+        let food = Coords::random::<W, H>(&mut self.rng, taken);
+        self.food = food;
+        for game in self.games.iter_mut() {
+            game.set_food(food);
+        }
+    }
+
+    // Everything both boards have to agree on, squashed into a byte
+    pub fn checksum(&self) -> u8 {
+        let mut hash: u32 = 0x811c_9dc5;
+        let mut feed = |byte: u8| hash = (hash ^ byte as u32).wrapping_mul(0x0100_0193);
+        for game in &self.games {
+            let snake = game.snake();
+            feed(coords_byte(snake.head));
+            for cell in snake.tail() {
+                feed(coords_byte(cell));
+            }
+            feed(game.score as u8);
+        }
+        feed(coords_byte(self.food));
+        self.step.to_le_bytes().into_iter().for_each(&mut feed);
+        (hash ^ (hash >> 8) ^ (hash >> 16) ^ (hash >> 24)) as u8
+    }
+
+    // Seen from one board: the own snake bright, the other one dim
+    pub fn matrix(&self, me: Player) -> [[u8; W]; H] {
+        let mut values = [[0; W]; H];
+        let other = match me {
+            Player::First => Player::Second,
+            Player::Second => Player::First,
+        };
+        for (player, head, tail) in [(other, 2, 1), (me, 6, 3)] {
+            let snake = self.game(player).snake();
+            for t in snake.tail() {
+                values[t.row as usize][t.col as usize] = tail;
+            }
+            values[snake.head.row as usize][snake.head.col as usize] = head;
+        }
+        values[self.food.row as usize][self.food.col as usize] = 9;
+        values
+    }
+}
+
+fn coords_byte(coords: Coords) -> u8 {
+    ((coords.row as u8) << 4) | (coords.col as u8 & 0x0f)
+}
+
+fn byte_coords(byte: u8) -> Coords {
+    Coords::new((byte >> 4) as i8, (byte & 0x0f) as i8)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepFrame {
+    pub step: u16,
+    pub turn: Turn,
+    // The sender's turn for step - 1
+    pub prev_turn: Turn,
+    // Food and checksum of the sender's game before the step
+    pub food: Coords,
+    pub check: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    // Sent until the other board answers, the larger nonce plays first
+    Hello { nonce: u32 },
+    Step(StepFrame),
+}
+
+// Frame layout: SYNC | kind | 6 bytes payload | crc8
+pub const FRAME_LEN: usize = 9;
+const HELLO: u8 = 0x01;
+const STEP: u8 = 0x02;
+
+fn turn_bits(turn: Turn) -> u8 {
+    match turn {
+        Turn::None => 0,
+        Turn::Left => 1,
+        Turn::Right => 2,
+    }
+}
+
+fn bits_turn(bits: u8) -> Option<Turn> {
+    match bits & 0b11 {
+        0 => Some(Turn::None),
+        1 => Some(Turn::Left),
+        2 => Some(Turn::Right),
+        _ => None,
+    }
+}
+
+impl Message {
+    pub fn encode(&self) -> [u8; FRAME_LEN] {
+        let mut frame = [0; FRAME_LEN];
+        match self {
+            Message::Hello { nonce } => {
+                frame[1] = HELLO;
+                frame[2..6].copy_from_slice(&nonce.to_le_bytes());
+            },
+            Message::Step(step) => {
+                frame[1] = STEP;
+                frame[2..4].copy_from_slice(&step.step.to_le_bytes());
+                frame[4] = turn_bits(step.turn) | (turn_bits(step.prev_turn) << 2);
+                frame[5] = coords_byte(step.food);
+                frame[6] = step.check;
+            },
+        }
+        serial_frame::seal(&mut frame);
+        frame
+    }
+
+    // Sync byte and crc are checked by the decoder already
+    fn decode(frame: &[u8; FRAME_LEN]) -> Option<Self> {
+        match frame[1] {
+            HELLO => Some(Message::Hello { nonce: u32::from_le_bytes([frame[2], frame[3], frame[4], frame[5]]) }),
+            STEP => Some(Message::Step(StepFrame {
+                step: u16::from_le_bytes([frame[2], frame[3]]),
+                turn: bits_turn(frame[4])?,
+                prev_turn: bits_turn(frame[4] >> 2)?,
+                food: byte_coords(frame[5]),
+                check: frame[6],
+            })),
+            _ => None,
+        }
+    }
+}
+
+// Collects bytes from the line into messages, garbage and broken frames are skipped
+#[derive(Debug, Default)]
+pub struct MessageDecoder {
+    frames: FrameDecoder<FRAME_LEN>,
+}
+
+impl MessageDecoder {
+    pub const fn new() -> Self {
+        Self { frames: FrameDecoder::new() }
+    }
+
+    pub fn feed(&mut self, byte: u8) -> Option<Message> {
+        Message::decode(&self.frames.feed(byte)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkError {
+    // The boards do not agree on the game any more
+    Desync { step: u16 },
+    // Nothing came in for LOST_AFTER polls
+    Lost { step: u16 },
+}
+
+pub const LOST_AFTER: u16 = 50;
+
+// One board's end of the lockstep protocol, what goes over the line is up to the caller
+pub struct Link {
+    nonce: u32,
+    peer_nonce: Option<u32>,
+    // The other board is still waiting for our hello
+    hello_wanted: bool,
+    step: u16,
+    // Our turn and game state for the current step, None until the game picked the turn
+    turn: Option<(Turn, Coords, u8)>,
+    prev_turn: Turn,
+    // Sent again while there is no turn for the current step yet, keeps a board that missed it going
+    last_frame: Option<StepFrame>,
+    peer_turn: Option<Turn>,
+    // Food and checksum the other board sent for the current step
+    peer_state: Option<(Coords, u8)>,
+    // The same from a frame for the next step, the other board is one ahead then
+    peer_state_ahead: Option<(Coords, u8)>,
+    quiet_polls: u16,
+}
+
+impl Link {
+    // The nonce should be random, it picks the seed and who plays first
+    pub fn new(nonce: u32) -> Self {
+        Self {
+            nonce,
+            peer_nonce: None,
+            hello_wanted: false,
+            step: 0,
+            turn: None,
+            prev_turn: Turn::None,
+            last_frame: None,
+            peer_turn: None,
+            peer_state: None,
+            peer_state_ahead: None,
+            quiet_polls: 0,
+        }
+    }
+
+    // Seed and own player once both boards know each other
+    pub fn start(&self) -> Option<(u32, Player)> {
+        let peer = self.peer_nonce?;
+        let player = if self.nonce > peer { Player::First } else { Player::Second };
+        Some((self.nonce ^ peer, player))
+    }
+
+    pub fn step(&self) -> u16 {
+        self.step
+    }
+
+    // What to send now, to be called regularly as frames get lost
+    pub fn outgoing(&mut self) -> Option<Message> {
+        if self.peer_nonce.is_none() || self.hello_wanted {
+            self.hello_wanted = false;
+            return Some(Message::Hello { nonce: self.nonce });
+        }
+        match self.turn {
+            Some((turn, food, check)) => Some(Message::Step(self.frame(turn, food, check))),
+            None => self.last_frame.map(Message::Step),
+        }
+    }
+
+    fn frame(&self, turn: Turn, food: Coords, check: u8) -> StepFrame {
+        StepFrame { step: self.step, turn, prev_turn: self.prev_turn, food, check }
+    }
+
+    pub fn receive(&mut self, message: Message) -> Result<(), LinkError> {
+        self.quiet_polls = 0;
+        match message {
+            Message::Hello { nonce } => {
+                if self.peer_nonce.is_some_and(|n| n != nonce) || nonce == self.nonce {
+                    return Err(LinkError::Desync { step: self.step });
+                }
+                if self.peer_nonce.is_some() {
+                    self.hello_wanted = true;
+                }
+                self.peer_nonce = Some(nonce);
+            },
+            Message::Step(frame) => {
+                if self.peer_nonce.is_none() {
+                    // Our hello arrived but theirs did not, keep saying hello until it does
+                    return Ok(());
+                }
+                if frame.step == self.step {
+                    self.peer_turn = Some(frame.turn);
+                    self.peer_state = Some((frame.food, frame.check));
+                } else if frame.step == self.step.wrapping_add(1) {
+                    self.peer_turn = Some(frame.prev_turn);
+                    // Checked once we are at that step too, the frame for it may never come again
+                    self.peer_state_ahead = Some((frame.food, frame.check));
+                } else if frame.step.wrapping_add(1) != self.step {
+                    return Err(LinkError::Desync { step: self.step });
+                }
+            },
+        }
+        Ok(())
+    }
+
+    // Call when a poll went by without anything coming in
+    pub fn poll_timeout(&mut self) -> Result<(), LinkError> {
+        self.quiet_polls += 1;
+        if self.quiet_polls > LOST_AFTER {
+            Err(LinkError::Lost { step: self.step })
+        } else {
+            Ok(())
+        }
+    }
+
+    // Our turn for the current step along with our game as it is before the step
+    pub fn send_turn<const W: usize, const H: usize>(&mut self, turn: Turn, versus: &Versus<W, H>) {
+        if self.turn.is_none() {
+            self.turn = Some((turn, versus.food(), versus.checksum()));
+        }
+    }
+
+    pub fn has_turn(&self) -> bool {
+        self.turn.is_some()
+    }
+
+    // Both turns in player order once they are known, then the caller steps and calls next_step
+    pub fn ready(&self) -> Result<Option<[Turn; 2]>, LinkError> {
+        let (Some((turn, food, check)), Some(peer_turn), Some((_, player))) = (self.turn, self.peer_turn, self.start())
+        else {
+            return Ok(None);
+        };
+        if self.peer_state.is_some_and(|state| state != (food, check)) {
+            return Err(LinkError::Desync { step: self.step });
+        }
+        Ok(Some(match player {
+            Player::First => [turn, peer_turn],
+            Player::Second => [peer_turn, turn],
+        }))
+    }
+
+    pub fn next_step(&mut self) {
+        if let Some((turn, food, check)) = self.turn.take() {
+            self.last_frame = Some(self.frame(turn, food, check));
+            self.prev_turn = turn;
+        }
+        self.peer_turn = None;
+        self.peer_state = self.peer_state_ahead.take();
+        self.step = self.step.wrapping_add(1);
+    }
+}
@@ -0,0 +1,96 @@
+use snake_core::{
+    Game,
+    app::{Action, App, AppState, Difficulty},
+    input::Press,
+    movement::Turn,
+    rules::GameRules,
+};
+
+// Feeds presses one after the other and collects what the app asked for
+fn script(app: &mut App, presses: &[Press]) -> Vec<Action> {
+    presses.iter().map(|p| app.press(*p)).collect()
+}
+
+#[test]
+fn starts_on_the_title() {
+    let app = App::default();
+    assert_eq!(app.state(), AppState::Title);
+    assert_eq!(app.difficulty(), Difficulty::Normal);
+}
+
+#[test]
+fn a_picks_difficulty_and_b_starts() {
+    let mut app = App::default();
+    let actions = script(&mut app, &[Press::A, Press::A, Press::A, Press::AB, Press::B]);
+    assert_eq!(
+        actions,
+        [
+            Action::ChangeDifficulty(Difficulty::Hard),
+            Action::ChangeDifficulty(Difficulty::Survival),
+            Action::ChangeDifficulty(Difficulty::Easy),
+            Action::Nothing,
+            Action::StartGame(Difficulty::Easy),
+        ]
+    );
+    assert_eq!(app.state(), AppState::Playing);
+}
+
+#[test]
+fn buttons_steer_while_playing() {
+    let mut app = App::default();
+    app.press(Press::B);
+    assert_eq!(script(&mut app, &[Press::A, Press::B]), [Action::Steer(Turn::Left), Action::Steer(Turn::Right)]);
+}
+
+#[test]
+fn both_buttons_toggle_pause() {
+    let mut app = App::default();
+    app.press(Press::B);
+    let actions = script(&mut app, &[Press::AB, Press::A, Press::B, Press::AB, Press::A]);
+    assert_eq!(
+        actions,
+        [Action::Pause, Action::Nothing, Action::Nothing, Action::Resume, Action::Steer(Turn::Left)]
+    );
+    assert_eq!(app.state(), AppState::Playing);
+}
+
+#[test]
+fn game_over_waits_for_a_press() {
+    let mut app = App::new(Difficulty::Hard);
+    app.press(Press::B);
+    app.game_over();
+    assert_eq!(app.state(), AppState::GameOver);
+    assert_eq!(app.press(Press::AB), Action::ShowTitle);
+    assert_eq!(app.state(), AppState::Title);
+    // The difficulty stays picked for the next game
+    assert_eq!(app.press(Press::B), Action::StartGame(Difficulty::Hard));
+}
+
+#[test]
+fn game_over_only_ends_a_game_in_play() {
+    let mut app = App::default();
+    app.game_over();
+    assert_eq!(app.state(), AppState::Title);
+    app.press(Press::B);
+    app.press(Press::AB);
+    app.game_over();
+    assert_eq!(app.state(), AppState::Paused);
+}
+
+#[test]
+fn normal_is_the_classic_curve() {
+    let game: Game = Game::new(1, GameRules::CLASSIC);
+    assert_eq!(Difficulty::Normal.speed_curve().step_len_ms(&game), game.step_len_ms());
+}
+
+#[test]
+fn difficulties_cycle_through_all() {
+    let mut difficulty = Difficulty::Easy;
+    let mut seen = vec![];
+    for _ in 0..4 {
+        seen.push(difficulty);
+        difficulty = difficulty.next();
+    }
+    assert_eq!(difficulty, Difficulty::Easy);
+    assert_eq!(seen, [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Survival]);
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use snake_core::{
+    Game,
+    autopilot::{Autopilot, Strategy, hamiltonian_cycle},
+    coords::Coords,
+    level::LEVELS,
+    movement::{Direction, GameStatus},
+    rules::GameRules,
+};
+
+fn play<const W: usize, const H: usize>(mut game: Game<W, H>, strategy: Strategy, max_steps: usize) -> Game<W, H> {
+    let mut autopilot = Autopilot::new(strategy);
+    for _ in 0..max_steps {
+        if game.status != GameStatus::Ongoing {
+            break;
+        }
+        game.step(autopilot.next_turn(&game));
+    }
+    game
+}
+
+// Follows the cycle from the top left corner and checks it comes back after visiting every cell once
+fn assert_full_cycle<const W: usize, const H: usize>(cycle: [[Direction; W]; H]) {
+    let mut seen = HashSet::new();
+    let mut at = Coords::new(0, 0);
+    for _ in 0..W * H {
+        assert!(seen.insert(at), "{at:?} visited twice");
+        let (row, col) = match cycle[at.row as usize][at.col as usize] {
+            Direction::Up => (at.row - 1, at.col),
+            Direction::Right => (at.row, at.col + 1),
+            Direction::Down => (at.row + 1, at.col),
+            Direction::Left => (at.row, at.col - 1),
+        };
+        at = Coords::new(row.rem_euclid(H as i8), col.rem_euclid(W as i8));
+    }
+    assert_eq!(at, Coords::new(0, 0));
+    assert_eq!(seen.len(), W * H);
+}
+
+#[test]
+fn cycles_cover_the_board() {
+    assert_full_cycle(hamiltonian_cycle(&Game::<5, 5>::new(1, GameRules::CLASSIC)).unwrap());
+    assert_full_cycle(hamiltonian_cycle(&Game::<8, 8>::new(1, GameRules::WALLS)).unwrap());
+    assert_full_cycle(hamiltonian_cycle(&Game::<5, 4>::new(1, GameRules::WALLS)).unwrap());
+    assert_full_cycle(hamiltonian_cycle(&Game::<4, 5>::new(1, GameRules::WALLS)).unwrap());
+    assert_full_cycle(hamiltonian_cycle(&Game::<5, 10>::new(1, GameRules::CLASSIC)).unwrap());
+}
+
+#[test]
+fn odd_board_without_wrapping_has_no_cycle() {
+    assert!(hamiltonian_cycle(&Game::<5, 5>::new(1, GameRules::WALLS)).is_none());
+    assert!(hamiltonian_cycle(&Game::<3, 5>::new(1, GameRules::CLASSIC)).is_none());
+}
+
+#[test]
+fn walls_in_the_level_have_no_cycle() {
+    let mut game: Game = Game::with_levels(1, GameRules::LEVELS, &LEVELS);
+    assert!(hamiltonian_cycle(&game).is_some());
+    game = Game::with_levels(1, GameRules::LEVELS, &LEVELS[1..]);
+    assert!(hamiltonian_cycle(&game).is_none());
+}
+
+#[test]
+fn hamiltonian_always_wins_on_5x5() {
+    for seed in 1..=200 {
+        let game = play(Game::<5, 5>::new(seed, GameRules::CLASSIC), Strategy::Hamiltonian, 1000);
+        assert_eq!(game.status, GameStatus::Won, "seed {seed}");
+    }
+}
+
+#[test]
+fn hamiltonian_wins_on_even_board_with_walls() {
+    for seed in 1..=20 {
+        let game = play(Game::<6, 6>::new(seed, GameRules::WALLS), Strategy::Hamiltonian, 2000);
+        assert_eq!(game.status, GameStatus::Won, "seed {seed}");
+    }
+}
+
+#[test]
+fn shortest_path_goes_straight_for_the_food() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    // Head at (2, 2) going right
+    game.set_food(Coords::new(2, 4));
+    let game = play(game, Strategy::ShortestPath, 2);
+    assert_eq!(game.score, 1);
+}
+
+#[test]
+fn shortest_path_does_not_hit_walls() {
+    let mut game: Game = Game::new(1, GameRules::WALLS);
+    game.set_food(Coords::new(0, 0));
+    // Going right towards the edge, the autopilot has to turn in time
+    let mut autopilot = Autopilot::new(Strategy::ShortestPath);
+    for _ in 0..4 {
+        game.step(autopilot.next_turn(&game));
+        assert_eq!(game.status, GameStatus::Ongoing);
+    }
+}
+
+#[test]
+fn shortest_path_mostly_wins_and_never_stalls() {
+    let mut won = 0;
+    for seed in 1..=50 {
+        let game = play(Game::<5, 5>::new(seed, GameRules::WALLS), Strategy::ShortestPath, 5000);
+        assert_ne!(game.status, GameStatus::Ongoing, "seed {seed}");
+        if game.status == GameStatus::Won {
+            won += 1;
+        }
+    }
+    assert!(won >= 35, "won {won} of 50");
+}
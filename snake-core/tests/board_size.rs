@@ -0,0 +1,107 @@
+use snake_core::{
+    Game,
+    coords::Coords,
+    movement::{Direction, GameStatus, Turn},
+    rules::GameRules,
+};
+
+#[test]
+fn default_board_is_the_micro_bit_display() {
+    let game: Game = Game::new(1, GameRules::CLASSIC);
+    let matrix: [[u8; 5]; 5] = game.game_matrix(6, 3, 9, 1);
+    assert_eq!(matrix[2][2], 6);
+}
+
+#[test]
+fn eight_by_eight_starts_in_the_middle_and_wraps() {
+    let mut game: Game<8, 8> = Game::new(1, GameRules::CLASSIC);
+    assert_eq!(game.snake().head, Coords::new(4, 4));
+    assert_eq!(game.snake().tail_end(), Some(Coords::new(4, 3)));
+    game.set_food(Coords::new(0, 0));
+    for _ in 0..3 {
+        game.step(Turn::None);
+    }
+    assert_eq!(game.snake().head, Coords::new(4, 7));
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(4, 0));
+    assert_eq!(game.status, GameStatus::Ongoing);
+}
+
+#[test]
+fn wide_board_wraps_rows_and_columns_separately() {
+    let mut game: Game<7, 3> = Game::new(1, GameRules::CLASSIC);
+    assert_eq!(game.snake().head, Coords::new(1, 3));
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::Right);
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(0, 3));
+    game.step(Turn::Left);
+    game.step(Turn::None);
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(0, 6));
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(0, 0));
+}
+
+#[test]
+fn walls_follow_the_board_size() {
+    let mut game: Game<8, 4> = Game::new(1, GameRules::WALLS);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::Right);
+    assert_eq!(game.snake().head, Coords::new(3, 4));
+    assert_eq!(game.status, GameStatus::Ongoing);
+    game.step(Turn::None);
+    assert_eq!(game.status, GameStatus::Lost);
+}
+
+#[test]
+fn start_length_is_capped_to_one_row() {
+    let rules = GameRules { start_length: 10, start_direction: Direction::Left, ..GameRules::CLASSIC };
+    let game: Game<6, 6> = Game::new(1, rules);
+    assert_eq!(game.snake().tail_len(), 5);
+    assert_eq!(game.snake().tail().count(), 5);
+}
+
+#[test]
+fn filling_a_small_board_is_won() {
+    // On a 3x3 board a snake eating every step fills it, the last move wraps over the bottom edge
+    let mut game: Game<3, 3> = Game::new(1, GameRules::CLASSIC);
+    let path = [
+        (Turn::Left, (0, 1)),
+        (Turn::Right, (0, 2)),
+        (Turn::Right, (1, 2)),
+        (Turn::None, (2, 2)),
+        (Turn::Right, (2, 1)),
+        (Turn::None, (2, 0)),
+        (Turn::Left, (0, 0)),
+    ];
+    for (turn, (row, col)) in path {
+        assert_eq!(game.status, GameStatus::Ongoing);
+        game.set_food(Coords::new(row, col));
+        game.step(turn);
+    }
+    assert_eq!(game.status, GameStatus::Won);
+    assert_eq!(game.score, 6);
+}
+
+#[test]
+fn tail_is_listed_from_the_end_to_the_head() {
+    let rules = GameRules { start_length: 4, ..GameRules::CLASSIC };
+    let mut game: Game<8, 8> = Game::new(1, rules);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::Right);
+    let tail: Vec<Coords> = game.snake().tail().collect();
+    assert_eq!(tail, [Coords::new(4, 2), Coords::new(4, 3), Coords::new(4, 4)]);
+    assert_eq!(game.snake().head, Coords::new(5, 4));
+}
+
+#[test]
+fn matrices_take_the_board_size() {
+    let mut game: Game<8, 2> = Game::new(1, GameRules::CLASSIC);
+    game.set_food(Coords::new(0, 7));
+    let matrix = game.game_matrix(6, 3, 9, 1);
+    assert_eq!(matrix, [[0, 0, 0, 0, 0, 0, 0, 9], [0, 0, 0, 3, 6, 0, 0, 0]]);
+
+    game.score = 10;
+    assert_eq!(game.score_matrix(), [[1; 8], [1, 1, 0, 0, 0, 0, 0, 0]]);
+}
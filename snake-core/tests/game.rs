@@ -0,0 +1,202 @@
+use snake_core::{
+    Game,
+    coords::Coords,
+    movement::{Direction, GameStatus, Turn},
+    rules::GameRules,
+};
+
+// Put the food right where the head is going next, then step onto it
+fn eat_ahead(game: &mut Game, turn: Turn) {
+    let head = game.snake().head;
+    let direction = match (game.snake().direction, turn) {
+        (d, Turn::None) => d,
+        (Direction::Up, Turn::Right) | (Direction::Down, Turn::Left) => Direction::Right,
+        (Direction::Right, Turn::Right) | (Direction::Left, Turn::Left) => Direction::Down,
+        (Direction::Down, Turn::Right) | (Direction::Up, Turn::Left) => Direction::Left,
+        (Direction::Left, Turn::Right) | (Direction::Right, Turn::Left) => Direction::Up,
+    };
+    let next = match direction {
+        Direction::Up => Coords::new((head.row + 4) % 5, head.col),
+        Direction::Down => Coords::new((head.row + 1) % 5, head.col),
+        Direction::Left => Coords::new(head.row, (head.col + 4) % 5),
+        Direction::Right => Coords::new(head.row, (head.col + 1) % 5),
+    };
+    game.set_food(next);
+    game.step(turn);
+}
+
+#[test]
+fn custom_start_survives_a_reset() {
+    let mut game: Game = Game::with_start(7, GameRules::CLASSIC, Coords::new(1, 1));
+    game.step(Turn::Left);
+    game.step(Turn::None);
+    game.reset();
+    assert_eq!(game.snake().head, Coords::new(1, 1));
+    assert_eq!(game.snake().direction, Direction::Right);
+    assert_eq!(game.status, GameStatus::Ongoing);
+}
+
+#[test]
+fn starts_in_the_middle_facing_right() {
+    let game: Game = Game::new(42, GameRules::CLASSIC);
+    assert_eq!(game.snake().head, Coords::new(2, 2));
+    assert_eq!(game.snake().tail_end(), Some(Coords::new(2, 1)));
+    assert_eq!(game.snake().direction, Direction::Right);
+    assert_eq!(game.status, GameStatus::Ongoing);
+    assert_eq!(game.score, 0);
+    assert!(!game.snake().contains(&game.food()));
+}
+
+#[test]
+fn same_seed_gives_same_food() {
+    assert_eq!(Game::<5, 5>::new(7, GameRules::CLASSIC).food(), Game::<5, 5>::new(7, GameRules::CLASSIC).food());
+}
+
+#[test]
+fn moves_one_cell_per_step() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(2, 3));
+    assert_eq!(game.snake().tail_end(), Some(Coords::new(2, 2)));
+    assert_eq!(game.snake().tail_len(), 1);
+}
+
+#[test]
+fn turns_are_relative_to_the_heading() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::Right);
+    assert_eq!(game.snake().direction, Direction::Down);
+    assert_eq!(game.snake().head, Coords::new(3, 2));
+    game.step(Turn::Left);
+    assert_eq!(game.snake().direction, Direction::Right);
+    assert_eq!(game.snake().head, Coords::new(3, 3));
+    game.step(Turn::Left);
+    assert_eq!(game.snake().direction, Direction::Up);
+    assert_eq!(game.snake().head, Coords::new(2, 3));
+}
+
+#[test]
+fn wraps_around_every_edge() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    game.set_food(Coords::new(1, 1));
+    // Right edge
+    game.step(Turn::None);
+    game.step(Turn::None);
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(2, 0));
+    // Bottom edge
+    game.step(Turn::Right);
+    game.step(Turn::None);
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(0, 0));
+    // Left edge
+    game.step(Turn::Right);
+    assert_eq!(game.snake().head, Coords::new(0, 4));
+    // Top edge
+    game.step(Turn::Right);
+    assert_eq!(game.snake().head, Coords::new(4, 4));
+    assert_eq!(game.status, GameStatus::Ongoing);
+}
+
+#[test]
+fn eating_grows_the_snake_and_moves_the_food() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    eat_ahead(&mut game, Turn::None);
+    assert_eq!(game.score, 1);
+    assert_eq!(game.snake().head, Coords::new(2, 3));
+    assert_eq!(game.snake().tail_len(), 2);
+    assert_eq!(game.snake().tail_end(), Some(Coords::new(2, 1)));
+    assert!(!game.snake().contains(&game.food()));
+    assert_eq!(game.status, GameStatus::Ongoing);
+}
+
+#[test]
+fn running_into_the_tail_is_lost() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    // Grow to five cells in row 2, then curl back into the body
+    eat_ahead(&mut game, Turn::None);
+    eat_ahead(&mut game, Turn::None);
+    eat_ahead(&mut game, Turn::Right);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::Right);
+    assert_eq!(game.status, GameStatus::Ongoing);
+    game.step(Turn::Right);
+    assert_eq!(game.status, GameStatus::Lost);
+}
+
+#[test]
+fn following_the_tail_end_is_allowed() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    // Four cells in a 2x2 square, so the head always lands on the cell the tail just left
+    eat_ahead(&mut game, Turn::None);
+    eat_ahead(&mut game, Turn::Right);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::Right);
+    for _ in 0..8 {
+        game.step(Turn::Right);
+        assert_eq!(game.status, GameStatus::Ongoing);
+        assert_eq!(game.snake().tail_len() + 1, 4);
+    }
+}
+
+#[test]
+fn filling_the_board_is_won() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    // Row by row, stepping down and shifting one column each row, covers the whole torus
+    let row = [Turn::Right, Turn::Left, Turn::None, Turn::None, Turn::None];
+    let mut turns = [Turn::None; 3].into_iter().chain(row.into_iter().cycle());
+    while game.status == GameStatus::Ongoing {
+        eat_ahead(&mut game, turns.next().unwrap());
+    }
+    assert_eq!(game.status, GameStatus::Won);
+    assert_eq!(game.score, 22);
+    assert_eq!(game.snake().tail_len(), 23);
+}
+
+#[test]
+fn speeds_up_every_five_points() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    assert_eq!(game.step_len_ms(), 1000);
+    let expected = [(4, 1, 1000), (5, 2, 800), (9, 2, 800), (10, 3, 600), (15, 4, 400), (20, 5, 200)];
+    let row = [Turn::Right, Turn::Left, Turn::None, Turn::None, Turn::None];
+    let mut turns = [Turn::None; 3].into_iter().chain(row.into_iter().cycle());
+    for (score, speed, step_len) in expected {
+        while game.score < score {
+            eat_ahead(&mut game, turns.next().unwrap());
+        }
+        assert_eq!(game.speed(), speed);
+        assert_eq!(game.step_len_ms(), step_len);
+    }
+}
+
+#[test]
+fn reset_starts_over() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    eat_ahead(&mut game, Turn::None);
+    game.reset();
+    assert_eq!(game.score, 0);
+    assert_eq!(game.speed(), 1);
+    assert_eq!(game.snake().head, Coords::new(2, 2));
+    assert_eq!(game.snake().tail_len(), 1);
+    assert_eq!(game.status, GameStatus::Ongoing);
+}
+
+#[test]
+fn matrices_show_snake_food_and_score() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    game.set_food(Coords::new(0, 4));
+    let matrix = game.game_matrix(6, 3, 9, 1);
+    assert_eq!(matrix[2], [0, 3, 6, 0, 0]);
+    assert_eq!(matrix[0], [0, 0, 0, 0, 9]);
+
+    game.score = 7;
+    assert_eq!(game.score_matrix(), [
+        [1, 1, 1, 1, 1],
+        [1, 1, 0, 0, 0],
+        [0; 5],
+        [0; 5],
+        [0; 5],
+    ]);
+}
@@ -0,0 +1,203 @@
+use embedded_storage::nor_flash::{ErrorType, NorFlash, NorFlashError, NorFlashErrorKind, ReadNorFlash};
+use snake_core::highscore::{HighScoreStore, HighScores};
+
+const PAGE: usize = 256;
+
+// Behaves like NOR flash: writes can only clear bits, erasing sets a whole page back to 0xff
+struct FakeFlash {
+    memory: Vec<u8>,
+    erases: usize,
+}
+
+impl FakeFlash {
+    fn new(pages: usize) -> Self {
+        Self { memory: vec![0xff; pages * PAGE], erases: 0 }
+    }
+}
+
+#[derive(Debug)]
+struct OutOfBounds;
+
+impl NorFlashError for OutOfBounds {
+    fn kind(&self) -> NorFlashErrorKind {
+        NorFlashErrorKind::OutOfBounds
+    }
+}
+
+impl ErrorType for FakeFlash {
+    type Error = OutOfBounds;
+}
+
+impl ReadNorFlash for FakeFlash {
+    const READ_SIZE: usize = 1;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), OutOfBounds> {
+        let offset = offset as usize;
+        let source = self.memory.get(offset..offset + bytes.len()).ok_or(OutOfBounds)?;
+        bytes.copy_from_slice(source);
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        self.memory.len()
+    }
+}
+
+impl NorFlash for FakeFlash {
+    const WRITE_SIZE: usize = 4;
+    const ERASE_SIZE: usize = PAGE;
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), OutOfBounds> {
+        assert_eq!(from as usize % PAGE, 0);
+        assert_eq!(to as usize % PAGE, 0);
+        self.memory.get_mut(from as usize..to as usize).ok_or(OutOfBounds)?.fill(0xff);
+        self.erases += 1;
+        Ok(())
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), OutOfBounds> {
+        assert_eq!(offset as usize % Self::WRITE_SIZE, 0);
+        let offset = offset as usize;
+        let target = self.memory.get_mut(offset..offset + bytes.len()).ok_or(OutOfBounds)?;
+        for (t, b) in target.iter_mut().zip(bytes) {
+            *t &= *b;
+        }
+        Ok(())
+    }
+}
+
+fn table(scores: &[u16]) -> HighScores {
+    let mut table = HighScores::default();
+    for score in scores {
+        table.insert(*score);
+    }
+    table
+}
+
+#[test]
+fn table_keeps_the_best_scores_in_order() {
+    let mut table = HighScores::default();
+    assert_eq!(table.insert(5), Some(0));
+    assert_eq!(table.insert(9), Some(0));
+    assert_eq!(table.insert(7), Some(1));
+    assert_eq!(table.insert(0), None);
+    for score in [1, 2, 3] {
+        table.insert(score);
+    }
+    assert_eq!(table.scores(), &[9, 7, 5, 3, 2]);
+    assert_eq!(table.insert(2), None);
+    assert_eq!(table.insert(4), Some(3));
+    assert_eq!(table.scores(), &[9, 7, 5, 4, 3]);
+    assert_eq!(table.best(), 9);
+}
+
+#[test]
+fn blank_flash_is_an_empty_table() {
+    let mut store = HighScoreStore::new(FakeFlash::new(2), 0);
+    assert_eq!(store.load().unwrap(), HighScores::default());
+}
+
+#[test]
+fn saved_table_survives_a_reset() {
+    let mut store = HighScoreStore::new(FakeFlash::new(3), PAGE as u32);
+    store.load().unwrap();
+    let (_, place) = store.submit(12).unwrap();
+    assert_eq!(place, Some(0));
+    store.submit(8).unwrap();
+
+    // A new store over the same flash is what the next boot sees
+    let mut store = HighScoreStore::new(store.free(), PAGE as u32);
+    assert_eq!(store.load().unwrap(), table(&[12, 8]));
+    let (table, place) = store.submit(3).unwrap();
+    assert_eq!(place, Some(2));
+    assert_eq!(table.scores(), &[12, 8, 3, 0, 0]);
+}
+
+#[test]
+fn stays_inside_its_pages() {
+    let mut store = HighScoreStore::new(FakeFlash::new(4), PAGE as u32);
+    store.load().unwrap();
+    for score in 1..100 {
+        store.submit(score).unwrap();
+    }
+    let flash = store.free();
+    assert!(flash.memory[..PAGE].iter().all(|b| *b == 0xff));
+    assert!(flash.memory[3 * PAGE..].iter().all(|b| *b == 0xff));
+}
+
+#[test]
+fn writes_are_spread_over_the_page() {
+    let mut store = HighScoreStore::new(FakeFlash::new(2), 0);
+    store.load().unwrap();
+    // 16 records fit in a fake page, so 40 saves need only two erases
+    for score in 1..=40 {
+        store.submit(score).unwrap();
+    }
+    assert_eq!(store.saves(), 40);
+    let mut store = HighScoreStore::new(store.free(), 0);
+    assert_eq!(store.load().unwrap(), table(&[40, 39, 38, 37, 36]));
+    assert_eq!(store.free().erases, 2);
+}
+
+#[test]
+fn power_lost_after_an_erase_keeps_the_scores() {
+    let mut store = HighScoreStore::new(FakeFlash::new(2), 0);
+    store.load().unwrap();
+    // Fills the first page, the next save erases the second one
+    for score in 1..=16 {
+        store.submit(score).unwrap();
+    }
+    store.submit(17).unwrap();
+    let mut flash = store.free();
+    assert_eq!(flash.erases, 1);
+    // The record after the erase never made it
+    flash.memory[PAGE..PAGE + 16].fill(0xff);
+
+    let mut store = HighScoreStore::new(flash, 0);
+    assert_eq!(store.load().unwrap(), table(&[16, 15, 14, 13, 12]));
+    store.submit(18).unwrap();
+    let mut store = HighScoreStore::new(store.free(), 0);
+    assert_eq!(store.load().unwrap(), table(&[18, 16, 15, 14, 13]));
+}
+
+#[test]
+fn scores_that_do_not_place_are_not_written() {
+    let mut store = HighScoreStore::new(FakeFlash::new(2), 0);
+    for score in [10, 9, 8, 7, 6] {
+        store.submit(score).unwrap();
+    }
+    let saves = store.saves();
+    assert_eq!(store.submit(2).unwrap().1, None);
+    assert_eq!(store.saves(), saves);
+}
+
+#[test]
+fn corrupt_record_falls_back_to_the_one_before() {
+    let mut store = HighScoreStore::new(FakeFlash::new(2), 0);
+    store.submit(5).unwrap();
+    store.submit(6).unwrap();
+    let mut flash = store.free();
+    // A bit cleared in the newest record, like a write cut short by a reset
+    flash.memory[16 + 4] &= 0xfb;
+
+    let mut store = HighScoreStore::new(flash, 0);
+    assert_eq!(store.load().unwrap(), table(&[5]));
+    // The damaged slot is skipped, not written over
+    store.submit(7).unwrap();
+    let mut store = HighScoreStore::new(store.free(), 0);
+    assert_eq!(store.load().unwrap(), table(&[7, 5]));
+}
+
+#[test]
+fn garbage_page_is_an_empty_table() {
+    let mut flash = FakeFlash::new(2);
+    for (i, b) in flash.memory.iter_mut().enumerate() {
+        *b = (i * 7) as u8;
+    }
+    let mut store = HighScoreStore::new(flash, 0);
+    assert_eq!(store.load().unwrap(), HighScores::default());
+    // No blank slot left, so the next save starts over on an erased page
+    store.submit(3).unwrap();
+    let mut store = HighScoreStore::new(store.free(), 0);
+    assert_eq!(store.load().unwrap(), table(&[3]));
+}
@@ -0,0 +1,85 @@
+use snake_core::{
+    Game,
+    coords::Coords,
+    input::{KeyDecoder, ScriptedTurns, TurnSource, tilt_direction, turn_towards},
+    movement::{Direction, GameStatus, Turn},
+    rules::GameRules,
+};
+
+// Drives a game from any source, like the firmware main loop does
+fn play(game: &mut Game, source: &mut impl TurnSource, steps: usize) {
+    for _ in 0..steps {
+        if game.status != GameStatus::Ongoing {
+            break;
+        }
+        let turn = source.next_turn(game.snake().direction);
+        game.step(turn);
+    }
+}
+
+#[test]
+fn turn_towards_picks_the_shorter_side() {
+    assert_eq!(turn_towards(Direction::Right, Direction::Down), Turn::Right);
+    assert_eq!(turn_towards(Direction::Right, Direction::Up), Turn::Left);
+    assert_eq!(turn_towards(Direction::Up, Direction::Left), Turn::Left);
+    assert_eq!(turn_towards(Direction::Left, Direction::Up), Turn::Right);
+}
+
+#[test]
+fn turn_towards_never_reverses() {
+    for heading in [Direction::Up, Direction::Right, Direction::Down, Direction::Left] {
+        assert_eq!(turn_towards(heading, heading), Turn::None);
+    }
+    assert_eq!(turn_towards(Direction::Right, Direction::Left), Turn::None);
+    assert_eq!(turn_towards(Direction::Up, Direction::Down), Turn::None);
+}
+
+#[test]
+fn flat_board_does_not_steer() {
+    assert_eq!(tilt_direction(0, 0, 300), None);
+    assert_eq!(tilt_direction(-250, 290, 300), None);
+}
+
+#[test]
+fn stronger_tilt_axis_wins() {
+    assert_eq!(tilt_direction(500, 0, 300), Some(Direction::Right));
+    assert_eq!(tilt_direction(-500, 200, 300), Some(Direction::Left));
+    assert_eq!(tilt_direction(100, 400, 300), Some(Direction::Down));
+    assert_eq!(tilt_direction(350, -400, 300), Some(Direction::Up));
+}
+
+#[test]
+fn keys_and_arrows_turn() {
+    let mut keys = KeyDecoder::new();
+    let turns: Vec<_> = b"a\x1b[Cxd\x1b[DA".iter().filter_map(|b| keys.feed(*b)).collect();
+    assert_eq!(turns, [Turn::Left, Turn::Right, Turn::Right, Turn::Left, Turn::Left]);
+}
+
+#[test]
+fn broken_escape_sequence_is_dropped() {
+    let mut keys = KeyDecoder::new();
+    assert_eq!(keys.feed(0x1b), None);
+    assert_eq!(keys.feed(b'x'), None);
+    assert_eq!(keys.feed(b'C'), None);
+    assert_eq!(keys.feed(b'd'), Some(Turn::Right));
+}
+
+#[test]
+fn scripted_turns_go_straight_when_done() {
+    let mut source = ScriptedTurns::new(&[Turn::Left, Turn::Right]);
+    assert_eq!(source.next_turn(Direction::Up), Turn::Left);
+    assert!(!source.is_finished());
+    assert_eq!(source.next_turn(Direction::Up), Turn::Right);
+    assert!(source.is_finished());
+    assert_eq!(source.next_turn(Direction::Up), Turn::None);
+}
+
+#[test]
+fn scripted_turns_steer_a_game() {
+    let mut game: Game = Game::new(1, GameRules::WALLS);
+    // Head starts at (2, 2) going right
+    let mut source = ScriptedTurns::new(&[Turn::Right, Turn::Right]);
+    play(&mut game, &mut source, 2);
+    assert_eq!(game.snake().head, Coords::new(3, 1));
+    assert_eq!(game.snake().direction, Direction::Left);
+}
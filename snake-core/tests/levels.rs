@@ -0,0 +1,150 @@
+use snake_core::{
+    Game,
+    coords::Coords,
+    level::{LEVELS, Level},
+    movement::{GameStatus, Turn},
+    rules::GameRules,
+};
+
+static PILLAR: [Level; 2] = [
+    Level::parse("
+        .....
+        .....
+        .....
+        ....#
+        .....
+    "),
+    Level::parse("
+        ..#..
+        .....
+        .....
+        .....
+        ..#..
+    "),
+];
+
+#[test]
+fn parses_walls_from_ascii_art() {
+    const LEVEL: Level<4, 2> = Level::parse("#..#\n.##.");
+    let walls: Vec<Coords> = LEVEL.walls().collect();
+    assert_eq!(walls, [Coords::new(0, 0), Coords::new(0, 3), Coords::new(1, 1), Coords::new(1, 2)]);
+    assert_eq!(LEVEL.wall_count(), 4);
+    assert!(LEVEL.is_wall(&Coords::new(1, 1)));
+    assert!(!LEVEL.is_wall(&Coords::new(1, 0)));
+    assert!(!LEVEL.is_wall(&Coords::new(-1, 0)));
+}
+
+#[test]
+#[should_panic(expected = "wrong width")]
+fn rejects_short_rows() {
+    Level::<3, 2>::parse("...\n..");
+}
+
+#[test]
+#[should_panic(expected = "wrong number of rows")]
+fn rejects_missing_rows() {
+    Level::<3, 2>::parse("...");
+}
+
+#[test]
+#[should_panic(expected = "too many rows")]
+fn rejects_extra_rows() {
+    Level::<3, 1>::parse("...\n...");
+}
+
+#[test]
+#[should_panic(expected = "unknown character")]
+fn rejects_unknown_characters() {
+    Level::<3, 1>::parse(".x.");
+}
+
+#[test]
+fn built_in_levels_keep_the_start_free() {
+    for level in &LEVELS {
+        for col in 0..5 {
+            assert!(!level.is_wall(&Coords::new(2, col)));
+        }
+    }
+}
+
+#[test]
+fn running_into_a_wall_is_lost() {
+    let mut game: Game = Game::with_levels(1, GameRules::CLASSIC, &PILLAR);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::None);
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(2, 4));
+    game.step(Turn::Right);
+    assert_eq!(game.status, GameStatus::Lost);
+}
+
+#[test]
+fn food_never_lands_on_a_wall() {
+    for seed in 1..200 {
+        let game: Game = Game::with_levels(seed, GameRules::CLASSIC, &LEVELS[1..]);
+        assert!(!game.walls().is_wall(&game.food()), "seed {seed}");
+    }
+}
+
+#[test]
+fn advances_to_the_next_level_after_enough_food() {
+    let rules = GameRules { food_per_level: 2, ..GameRules::CLASSIC };
+    let mut game: Game = Game::with_levels(1, rules, &PILLAR);
+    assert_eq!(game.level(), 0);
+    game.set_food(Coords::new(2, 3));
+    game.step(Turn::None);
+    game.set_food(Coords::new(2, 4));
+    game.step(Turn::None);
+
+    // The snake starts over, the score stays
+    assert_eq!(game.level(), 1);
+    assert_eq!(game.score, 2);
+    assert_eq!(game.snake().head, Coords::new(2, 2));
+    assert_eq!(game.snake().tail_len(), 1);
+    assert!(game.walls().is_wall(&Coords::new(0, 2)));
+    assert!(!game.walls().is_wall(&game.food()));
+
+    // Stays on the last level
+    game.set_food(Coords::new(2, 3));
+    game.step(Turn::None);
+    game.set_food(Coords::new(2, 4));
+    game.step(Turn::None);
+    assert_eq!(game.level(), 1);
+    assert_eq!(game.snake().tail_len(), 3);
+
+    game.reset();
+    assert_eq!(game.level(), 0);
+}
+
+#[test]
+fn no_food_per_level_stays_on_the_first_level() {
+    let mut game: Game = Game::with_levels(1, GameRules::CLASSIC, &PILLAR);
+    for col in [3, 4] {
+        game.set_food(Coords::new(2, col));
+        game.step(Turn::None);
+    }
+    assert_eq!(game.level(), 0);
+}
+
+#[test]
+fn walls_count_against_the_win_length() {
+    const BOXED: [Level<3, 3>; 1] = [Level::parse("
+        ###
+        ...
+        ###
+    ")];
+    let mut game: Game<3, 3> = Game::with_levels(1, GameRules::CLASSIC, &BOXED);
+    game.set_food(Coords::new(1, 2));
+    game.step(Turn::None);
+    assert_eq!(game.status, GameStatus::Won);
+}
+
+#[test]
+fn walls_have_their_own_brightness() {
+    let mut game: Game = Game::with_levels(1, GameRules::CLASSIC, &PILLAR);
+    game.set_food(Coords::new(0, 0));
+    let matrix = game.game_matrix(6, 3, 9, 1);
+    assert_eq!(matrix[3], [0, 0, 0, 0, 1]);
+    assert_eq!(matrix[2], [0, 3, 6, 0, 0]);
+    assert_eq!(matrix[0], [9, 0, 0, 0, 0]);
+}
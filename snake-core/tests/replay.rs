@@ -0,0 +1,101 @@
+use snake_core::{
+    Game,
+    autopilot::{Autopilot, Strategy},
+    coords::Coords,
+    level::LEVELS,
+    movement::{GameStatus, Turn},
+    replay::{Replay, ReplayLog},
+    rules::GameRules,
+};
+
+// Written out by ReplayLog's Display on the host, for a classic game without levels. A dump from
+// the board has to be replayed with the rules and levels the firmware plays, see its main.rs.
+const CAPTURED: &str = "seed deadbeef turns .....LRL.LL....LL...R....LRL.RR.R..LL.LL.R...R.......RR........L....L...L..R.L..L.R..R....";
+
+#[test]
+fn captured_log_replays_to_the_same_end() {
+    let log: ReplayLog<256> = ReplayLog::parse(CAPTURED).unwrap();
+    assert_eq!(log.seed(), 0xdead_beef);
+    assert_eq!(log.turns().len(), 90);
+
+    let game: Game = log.replay(GameRules::CLASSIC, &[]).finish();
+    assert_eq!(game.status, GameStatus::Lost);
+    assert_eq!(game.score, 5);
+    assert_eq!(game.snake().head, Coords::new(2, 4));
+    assert_eq!(game.food(), Coords::new(3, 2));
+}
+
+#[test]
+fn replay_reproduces_every_frame() {
+    let turns = [Turn::None, Turn::Right, Turn::None, Turn::Left, Turn::Left, Turn::None, Turn::Right];
+    let mut game: Game = Game::new(0x1234_5678, GameRules::CLASSIC);
+    let mut log: ReplayLog<32> = ReplayLog::new(0x1234_5678);
+    let mut frames = Vec::new();
+    for turn in turns.iter().cycle().take(30) {
+        if game.status != GameStatus::Ongoing {
+            break;
+        }
+        log.record(*turn).unwrap();
+        game.step(*turn);
+        frames.push((game.game_matrix(6, 3, 9, 1), game.status, game.score));
+    }
+
+    let mut replay: Replay = log.replay(GameRules::CLASSIC, &[]);
+    for frame in &frames {
+        replay.step().unwrap();
+        let game = replay.game();
+        assert_eq!(&(game.game_matrix(6, 3, 9, 1), game.status, game.score), frame);
+    }
+    assert_eq!(replay.step(), None);
+}
+
+#[test]
+fn replay_follows_the_game_onto_the_next_level() {
+    let mut game: Game = Game::with_levels(0x0bad_cafe, GameRules::LEVELS, &LEVELS);
+    let mut autopilot = Autopilot::new(Strategy::ShortestPath);
+    let mut log: ReplayLog<512> = ReplayLog::new(0x0bad_cafe);
+    while game.status == GameStatus::Ongoing && game.level() < 2 && !log.is_full() {
+        let turn = autopilot.next_turn(&game);
+        log.record(turn).unwrap();
+        game.step(turn);
+    }
+    assert!(game.level() >= 1, "the autopilot never left the first level");
+
+    let replayed: Game = log.replay(GameRules::LEVELS, &LEVELS).finish();
+    assert_eq!(replayed.level(), game.level());
+    assert_eq!(replayed.game_matrix(6, 3, 9, 1), game.game_matrix(6, 3, 9, 1));
+    assert_eq!((replayed.status, replayed.score), (game.status, game.score));
+}
+
+#[test]
+fn dump_and_parse_round_trip() {
+    let mut log: ReplayLog<8> = ReplayLog::new(0xab);
+    for turn in [Turn::Left, Turn::None, Turn::Right] {
+        log.record(turn).unwrap();
+    }
+    let text = log.to_string();
+    assert_eq!(text, "seed 000000ab turns L.R");
+    assert_eq!(ReplayLog::parse(&text), Some(log));
+}
+
+#[test]
+fn parse_rejects_garbage() {
+    assert_eq!(ReplayLog::<8>::parse("seed xyz turns L"), None);
+    assert_eq!(ReplayLog::<8>::parse("seed 00000001 turns LX"), None);
+    assert_eq!(ReplayLog::<8>::parse("turns L"), None);
+    // More turns than the log can hold
+    assert_eq!(ReplayLog::<2>::parse("seed 00000001 turns LLL"), None);
+}
+
+#[test]
+fn full_log_hands_the_turn_back() {
+    let mut log: ReplayLog<2> = ReplayLog::new(1);
+    assert_eq!(log.record(Turn::Left), Ok(()));
+    assert_eq!(log.record(Turn::Right), Ok(()));
+    assert!(log.is_full());
+    assert_eq!(log.record(Turn::None), Err(Turn::None));
+
+    log.reset(2);
+    assert_eq!(log.seed(), 2);
+    assert!(log.turns().is_empty());
+}
@@ -0,0 +1,172 @@
+use snake_core::{
+    Game,
+    coords::Coords,
+    movement::{Direction, GameStatus, Turn},
+    rules::{Edges, GameRules},
+};
+
+// Put the food on the next cell in the current direction (no turns, no edges crossed)
+fn eat_straight(game: &mut Game) {
+    let head = game.snake().head;
+    let next = match game.snake().direction {
+        Direction::Up => Coords::new(head.row - 1, head.col),
+        Direction::Down => Coords::new(head.row + 1, head.col),
+        Direction::Left => Coords::new(head.row, head.col - 1),
+        Direction::Right => Coords::new(head.row, head.col + 1),
+    };
+    game.set_food(next);
+    game.step(Turn::None);
+}
+
+#[test]
+fn classic_is_the_default() {
+    assert_eq!(GameRules::default(), GameRules::CLASSIC);
+    assert_eq!(GameRules::CLASSIC.edges, Edges::Wrap);
+}
+
+#[test]
+fn walls_end_the_game_at_the_edge() {
+    let mut game: Game = Game::new(1, GameRules::WALLS);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::None);
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(2, 4));
+    assert_eq!(game.status, GameStatus::Ongoing);
+    game.step(Turn::None);
+    assert_eq!(game.status, GameStatus::Lost);
+}
+
+#[test]
+fn wraparound_passes_the_edge() {
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    game.set_food(Coords::new(0, 0));
+    for _ in 0..3 {
+        game.step(Turn::None);
+    }
+    assert_eq!(game.snake().head, Coords::new(2, 0));
+    assert_eq!(game.status, GameStatus::Ongoing);
+}
+
+#[test]
+fn start_length_and_direction() {
+    let rules = GameRules { start_length: 4, start_direction: Direction::Up, ..GameRules::CLASSIC };
+    let game: Game = Game::new(1, rules);
+    let snake = game.snake();
+    assert_eq!(snake.head, Coords::new(2, 2));
+    assert_eq!(snake.direction, Direction::Up);
+    assert_eq!(snake.tail_len(), 3);
+    // The end of the tail is furthest from the head and wraps over the bottom edge
+    assert_eq!(snake.tail_end(), Some(Coords::new(0, 2)));
+    for row in [3, 4, 0] {
+        assert!(snake.contains(&Coords::new(row, 2)));
+    }
+}
+
+#[test]
+fn start_tail_stops_at_a_wall() {
+    let rules = GameRules { edges: Edges::Walls, start_length: 4, start_direction: Direction::Right, ..GameRules::CLASSIC };
+    let game: Game = Game::with_start(1, rules, Coords::new(0, 1));
+    let snake = game.snake();
+    // Only the head and the cell left of it fit before the wall
+    assert_eq!(snake.tail_len(), 1);
+    assert_eq!(snake.tail_end(), Some(Coords::new(0, 0)));
+    assert!(!snake.contains(&Coords::new(0, 4)));
+
+    // Wrapping edges still lay it out across the edge
+    let game: Game = Game::with_start(1, GameRules { edges: Edges::Wrap, ..rules }, Coords::new(0, 1));
+    assert_eq!(game.snake().tail_len(), 3);
+    assert_eq!(game.snake().tail_end(), Some(Coords::new(0, 3)));
+}
+
+#[test]
+fn start_length_of_one_is_just_a_head() {
+    let rules = GameRules { start_length: 1, ..GameRules::CLASSIC };
+    let mut game: Game = Game::new(1, rules);
+    assert!(game.snake().tail_len() == 0);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::None);
+    assert_eq!(game.snake().head, Coords::new(2, 3));
+    assert!(game.snake().tail_len() == 0);
+    // Nothing follows the head, the cell it left is free again
+    assert!(!game.snake().contains(&Coords::new(2, 2)));
+}
+
+#[test]
+fn growth_is_spread_over_the_next_steps() {
+    let rules = GameRules { growth_per_food: 3, start_direction: Direction::Down, edges: Edges::Wrap, ..GameRules::CLASSIC };
+    let mut game: Game = Game::new(1, rules);
+    eat_straight(&mut game);
+    assert_eq!(game.snake().tail_len(), 2);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::Left);
+    assert_eq!(game.snake().tail_len(), 3);
+    game.step(Turn::None);
+    assert_eq!(game.snake().tail_len(), 4);
+    // Fully grown, from now on the length stays put
+    game.step(Turn::None);
+    assert_eq!(game.snake().tail_len(), 4);
+    assert_eq!(game.score, 1);
+}
+
+#[test]
+fn growing_snake_cannot_follow_its_tail_end() {
+    let rules = GameRules { growth_per_food: 3, ..GameRules::CLASSIC };
+    let mut game: Game = Game::new(1, rules);
+    // Eat, then curl around a 2x2 square, the tail end stays where it is while growing
+    eat_straight(&mut game);
+    game.set_food(Coords::new(0, 0));
+    game.step(Turn::Right);
+    game.step(Turn::Right);
+    assert_eq!(game.status, GameStatus::Ongoing);
+    game.step(Turn::Right);
+    assert_eq!(game.status, GameStatus::Lost);
+}
+
+#[test]
+fn win_length_ends_the_game_early() {
+    let rules = GameRules { win_length: 5, ..GameRules::CLASSIC };
+    let mut game: Game = Game::new(1, rules);
+    eat_straight(&mut game);
+    eat_straight(&mut game);
+    assert_eq!(game.status, GameStatus::Ongoing);
+    assert_eq!(game.snake().tail_len(), 3);
+    game.set_food(Coords::new(2, 0));
+    game.step(Turn::None);
+    assert_eq!(game.status, GameStatus::Won);
+}
+
+#[test]
+fn win_length_counts_pending_growth() {
+    let rules = GameRules { win_length: 5, growth_per_food: 2, ..GameRules::CLASSIC };
+    let mut game: Game = Game::new(1, rules);
+    eat_straight(&mut game);
+    assert_eq!(game.status, GameStatus::Ongoing);
+    eat_straight(&mut game);
+    assert_eq!(game.status, GameStatus::Won);
+}
+
+#[test]
+fn speed_step_is_configurable() {
+    let rules = GameRules { speed_up_every: 2, start_direction: Direction::Down, ..GameRules::CLASSIC };
+    let mut game: Game = Game::new(1, rules);
+    eat_straight(&mut game);
+    assert_eq!(game.speed(), 1);
+    eat_straight(&mut game);
+    assert_eq!(game.speed(), 2);
+
+    let rules = GameRules { speed_up_every: 0, start_direction: Direction::Down, ..GameRules::CLASSIC };
+    let mut game: Game = Game::new(1, rules);
+    eat_straight(&mut game);
+    eat_straight(&mut game);
+    assert_eq!(game.speed(), 1);
+}
+
+#[test]
+fn reset_keeps_the_rules() {
+    let rules = GameRules { start_length: 3, ..GameRules::WALLS };
+    let mut game: Game = Game::new(1, rules);
+    eat_straight(&mut game);
+    game.reset();
+    assert_eq!(game.rules(), &rules);
+    assert_eq!(game.snake().tail_len(), 2);
+}
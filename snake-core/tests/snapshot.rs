@@ -0,0 +1,87 @@
+use snake_core::{
+    Game,
+    level::LEVELS,
+    movement::{GameStatus, Turn},
+    rules::GameRules,
+    snapshot::{self, SnapshotError, snapshot_len},
+};
+
+const TURNS: [Turn; 7] = [Turn::None, Turn::Right, Turn::None, Turn::Left, Turn::Left, Turn::None, Turn::Right];
+
+fn played<const W: usize, const H: usize>(seed: u32, rules: GameRules, steps: usize) -> Game<W, H> {
+    let mut game = Game::new(seed, rules);
+    for turn in TURNS.iter().cycle().take(steps) {
+        if game.status != GameStatus::Ongoing {
+            break;
+        }
+        game.step(*turn);
+    }
+    game
+}
+
+#[test]
+fn snapshot_restores_the_same_game() {
+    let game: Game = played(0x1234_5678, GameRules::CLASSIC, 20);
+    let mut buf = [0; snapshot_len(5, 5)];
+    let len = game.snapshot(&mut buf).unwrap();
+
+    let restored: Game = Game::from_snapshot(&buf[..len], GameRules::CLASSIC, &[]).unwrap();
+    assert_eq!(restored.game_matrix(6, 3, 9, 1), game.game_matrix(6, 3, 9, 1));
+    assert_eq!(restored.snake().direction, game.snake().direction);
+    assert_eq!((restored.score, restored.speed(), restored.steps()), (game.score, game.speed(), game.steps()));
+    assert_eq!(restored.snake().tail().collect::<Vec<_>>(), game.snake().tail().collect::<Vec<_>>());
+}
+
+#[test]
+fn restored_game_plays_on_the_same_way() {
+    let mut game: Game<8, 8> = played(0xdead_beef, GameRules::CLASSIC, 40);
+    let mut buf = [0; snapshot_len(8, 8)];
+    let len = game.snapshot(&mut buf).unwrap();
+    let mut restored: Game<8, 8> = Game::from_snapshot(&buf[..len], GameRules::CLASSIC, &[]).unwrap();
+
+    // Same rng state, so the food turns up in the same places too
+    for turn in TURNS.iter().cycle().take(60) {
+        game.step(*turn);
+        restored.step(*turn);
+        assert_eq!(restored.game_matrix(6, 3, 9, 1), game.game_matrix(6, 3, 9, 1));
+        assert_eq!(restored.status, game.status);
+    }
+}
+
+#[test]
+fn snapshot_keeps_level_and_status() {
+    let mut game: Game = Game::with_levels(7, GameRules::LEVELS, &LEVELS);
+    for turn in TURNS.iter().cycle().take(500) {
+        game.step(*turn);
+    }
+    let mut buf = [0; snapshot_len(5, 5)];
+    let len = game.snapshot(&mut buf).unwrap();
+    let restored: Game = Game::from_snapshot(&buf[..len], GameRules::LEVELS, &LEVELS).unwrap();
+    assert_eq!(restored.status, game.status);
+    assert_eq!(restored.level(), game.level());
+    assert_eq!(restored.game_matrix(6, 3, 9, 1), game.game_matrix(6, 3, 9, 1));
+}
+
+#[test]
+fn short_buffer_is_refused() {
+    let game: Game = played(3, GameRules::CLASSIC, 10);
+    let mut buf = [0; 10];
+    assert_eq!(game.snapshot(&mut buf), Err(SnapshotError::TooShort));
+}
+
+#[test]
+fn damaged_snapshots_are_refused() {
+    let game: Game = played(0x1234_5678, GameRules::CLASSIC, 20);
+    let mut buf = [0; snapshot_len(5, 5)];
+    let len = game.snapshot(&mut buf).unwrap();
+
+    let mut flipped = buf;
+    flipped[9] ^= 0x01;
+    assert_eq!(Game::<5, 5>::from_snapshot(&flipped[..len], GameRules::CLASSIC, &[]).err(), Some(SnapshotError::BadChecksum));
+    assert_eq!(Game::<5, 5>::from_snapshot(&buf[..len - 1], GameRules::CLASSIC, &[]).err(), Some(SnapshotError::TooShort));
+    assert_eq!(
+        Game::<8, 8>::from_snapshot(&buf[..len], GameRules::CLASSIC, &[]).err(),
+        Some(SnapshotError::WrongSize { width: 5, height: 5 })
+    );
+    assert_eq!(snapshot::board_size(&buf[..len]), Some((5, 5)));
+}
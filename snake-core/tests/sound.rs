@@ -0,0 +1,106 @@
+use snake_core::{
+    Game,
+    coords::Coords,
+    movement::Turn,
+    rules::GameRules,
+    sound::{Edge, Note, Player, SoundEffect},
+};
+
+fn edges(effect: SoundEffect) -> Vec<Edge> {
+    let mut player = Player::new();
+    player.play(effect);
+    std::iter::from_fn(|| player.next_edge()).collect()
+}
+
+#[test]
+fn effects_last_as_long_as_their_notes() {
+    for effect in [SoundEffect::Eat, SoundEffect::Death, SoundEffect::Win, SoundEffect::SpeedUp] {
+        let total_us: u32 = edges(effect).iter().map(|e| e.wait_us).sum();
+        let notes_ms: u32 = effect.notes().iter().map(|n| n.ms).sum();
+        assert_eq!(total_us, notes_ms * 1000, "{effect:?}");
+    }
+}
+
+#[test]
+fn steady_tone_toggles_at_half_the_period() {
+    let mut player = Player::new();
+    player.play(SoundEffect::SpeedUp);
+    let first = player.next_edge().unwrap();
+    let second = player.next_edge().unwrap();
+    assert_eq!(first, Edge { high: true, wait_us: 500_000 / 1320 });
+    assert_eq!(second, Edge { high: false, wait_us: 500_000 / 1320 });
+}
+
+#[test]
+fn rests_keep_the_speaker_low() {
+    let all = edges(SoundEffect::SpeedUp);
+    let rest = all.iter().find(|e| e.wait_us == 30_000).unwrap();
+    assert!(!rest.high);
+}
+
+#[test]
+fn sweeps_change_pitch() {
+    let death = edges(SoundEffect::Death);
+    // Falling pitch means the half periods get longer
+    assert!(death.first().unwrap().wait_us < death.last().unwrap().wait_us);
+    let eat = edges(SoundEffect::Eat);
+    assert!(eat.first().unwrap().wait_us > eat.last().unwrap().wait_us);
+}
+
+#[test]
+fn a_new_effect_cuts_off_the_old_one() {
+    let mut player = Player::new();
+    assert!(!player.is_playing());
+    assert_eq!(player.next_edge(), None);
+    player.play(SoundEffect::Death);
+    player.next_edge();
+    player.play(SoundEffect::SpeedUp);
+    assert_eq!(player.next_edge().unwrap().wait_us, 500_000 / 1320);
+    player.stop();
+    assert!(!player.is_playing());
+}
+
+#[test]
+fn zero_length_notes_are_skipped() {
+    static TUNE: [Note; 4] = [Note::tone(1000, 0), Note::tone(500, 2), Note::sweep(100, 200, 0), Note::rest(0)];
+    let mut player = Player::new();
+    player.play_notes(&TUNE);
+    let all: Vec<Edge> = std::iter::from_fn(|| player.next_edge()).collect();
+    assert_eq!(all, [Edge { high: true, wait_us: 1000 }, Edge { high: false, wait_us: 1000 }]);
+    assert!(!player.is_playing());
+
+    player.play_notes(&TUNE[2..]);
+    assert_eq!(player.next_edge(), None);
+}
+
+#[test]
+fn notes_describe_tones_sweeps_and_rests() {
+    assert_eq!(Note::tone(440, 10), Note { from_hz: 440, to_hz: 440, ms: 10 });
+    assert_eq!(Note::sweep(440, 880, 10), Note { from_hz: 440, to_hz: 880, ms: 10 });
+    assert_eq!(Note::rest(10), Note { from_hz: 0, to_hz: 0, ms: 10 });
+}
+
+#[test]
+fn picks_the_effect_for_a_step() {
+    fn step(game: &mut Game, food: Coords) -> Option<SoundEffect> {
+        game.set_food(food);
+        let (score, speed) = (game.score, game.speed());
+        game.step(Turn::None);
+        SoundEffect::after_step(score, speed, game)
+    }
+
+    let rules = GameRules { speed_up_every: 2, ..GameRules::CLASSIC };
+    let mut game: Game = Game::new(1, rules);
+    assert_eq!(step(&mut game, Coords::new(0, 0)), None);
+    assert_eq!(step(&mut game, Coords::new(2, 4)), Some(SoundEffect::Eat));
+    assert_eq!(step(&mut game, Coords::new(2, 0)), Some(SoundEffect::SpeedUp));
+
+    let mut game: Game = Game::new(1, GameRules::WALLS);
+    step(&mut game, Coords::new(0, 0));
+    step(&mut game, Coords::new(0, 0));
+    assert_eq!(step(&mut game, Coords::new(0, 0)), Some(SoundEffect::Death));
+
+    let rules = GameRules { win_length: 3, ..GameRules::CLASSIC };
+    let mut game: Game = Game::new(1, rules);
+    assert_eq!(step(&mut game, Coords::new(2, 3)), Some(SoundEffect::Win));
+}
@@ -0,0 +1,124 @@
+use snake_core::{
+    Game,
+    app::Difficulty,
+    coords::Coords,
+    movement::Turn,
+    rules::GameRules,
+    speed::SpeedCurve,
+};
+
+const TABLE: SpeedCurve = SpeedCurve::stepped(&[900, 700, 500, 400]);
+
+fn curves() -> Vec<SpeedCurve> {
+    let mut curves = vec![
+        SpeedCurve::CLASSIC,
+        SpeedCurve::Linear { start_ms: 500, step_ms: 7, min_ms: 120 },
+        SpeedCurve::Exponential { start_ms: 1000, percent: 80, min_ms: 180 },
+        TABLE,
+        SpeedCurve::TimeBased { every_steps: 10, curve: &TABLE },
+    ];
+    curves.extend([Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Survival].map(|d| d.speed_curve()));
+    curves
+}
+
+#[test]
+fn curves_never_slow_down_and_stay_in_bounds() {
+    for curve in curves() {
+        let first = curve.step_len_at(1);
+        let mut last = first;
+        for level in 1..=1000 {
+            let ms = curve.step_len_at(level);
+            assert!(ms <= last, "{curve:?} slows down at level {level}");
+            assert!(ms > 0 && ms <= first, "{curve:?} out of bounds at level {level}");
+            last = ms;
+        }
+        assert!(curve.step_len_at(u32::MAX) <= last, "{curve:?}");
+    }
+}
+
+#[test]
+fn linear_stops_at_its_minimum() {
+    let curve = SpeedCurve::Linear { start_ms: 1000, step_ms: 300, min_ms: 250 };
+    assert_eq!(curve.step_len_at(1), 1000);
+    assert_eq!(curve.step_len_at(3), 400);
+    assert_eq!(curve.step_len_at(4), 250);
+    assert_eq!(curve.step_len_at(200), 250);
+}
+
+#[test]
+fn exponential_shrinks_by_percent() {
+    let curve = SpeedCurve::Exponential { start_ms: 1000, percent: 50, min_ms: 100 };
+    assert_eq!(curve.step_len_at(1), 1000);
+    assert_eq!(curve.step_len_at(2), 500);
+    assert_eq!(curve.step_len_at(3), 250);
+    assert_eq!(curve.step_len_at(5), 100);
+}
+
+#[test]
+fn table_holds_its_last_entry() {
+    assert_eq!(TABLE.step_len_at(0), 900);
+    assert_eq!(TABLE.step_len_at(2), 700);
+    assert_eq!(TABLE.step_len_at(4), 400);
+    assert_eq!(TABLE.step_len_at(99), 400);
+}
+
+#[test]
+#[should_panic(expected = "slows down")]
+fn table_that_slows_down_is_rejected() {
+    SpeedCurve::stepped(&[500, 400, 450]);
+}
+
+#[test]
+#[should_panic(expected = "empty")]
+fn empty_table_is_rejected() {
+    SpeedCurve::stepped(&[]);
+}
+
+#[test]
+fn hand_built_empty_table_plays_like_classic() {
+    let curve = SpeedCurve::Stepped(&[]);
+    for level in [1, 2, 5, 1000] {
+        assert_eq!(curve.step_len_at(level), SpeedCurve::CLASSIC.step_len_at(level));
+    }
+}
+
+#[test]
+fn exponential_does_not_overflow_or_spin() {
+    let huge = SpeedCurve::Exponential { start_ms: u32::MAX, percent: 90, min_ms: 100 };
+    assert!(huge.step_len_at(2) < u32::MAX);
+    assert!(huge.step_len_at(u32::MAX) >= 100);
+    // Nothing changes from level to level, so u32::MAX levels are no more work than one
+    let flat = SpeedCurve::Exponential { start_ms: 400, percent: 100, min_ms: 100 };
+    assert_eq!(flat.step_len_at(u32::MAX), 400);
+}
+
+#[test]
+fn classic_level_follows_the_score() {
+    // Wide enough to eat five times along one row without running into the tail
+    let mut game = Game::<10, 10>::new(1, GameRules::CLASSIC);
+    assert_eq!(SpeedCurve::CLASSIC.level(&game), 1);
+    assert_eq!(game.step_len_ms(), 1000);
+    for _ in 0..5 {
+        let head = game.snake().head;
+        game.set_food(Coords::new(head.row, (head.col + 1) % 10));
+        game.step(Turn::None);
+    }
+    assert_eq!(game.score, 5);
+    assert_eq!(SpeedCurve::CLASSIC.level(&game), 2);
+    assert_eq!(game.step_len_ms(), 800);
+}
+
+#[test]
+fn time_based_level_follows_the_steps() {
+    let curve = SpeedCurve::TimeBased { every_steps: 10, curve: &TABLE };
+    let mut game: Game = Game::new(1, GameRules::CLASSIC);
+    game.set_food(Coords::new(0, 0));
+    for _ in 0..9 {
+        game.step(Turn::None);
+    }
+    assert_eq!(curve.level(&game), 1);
+    game.step(Turn::None);
+    assert_eq!(game.steps(), 10);
+    assert_eq!(curve.level(&game), 2);
+    assert_eq!(curve.step_len_ms(&game), 700);
+}
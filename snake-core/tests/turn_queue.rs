@@ -0,0 +1,109 @@
+use heapless::spsc::Queue;
+use snake_core::{
+    input::Press,
+    movement::Turn,
+    turn_queue::{CHORD_MS, STALE_MS, TurnEvent, TurnReader},
+};
+
+// Queues the events and reads one turn per step at the given times
+fn read(events: &[(Turn, u32)], steps_at_ms: &[u32]) -> Vec<Turn> {
+    let mut queue: Queue<TurnEvent, 8> = Queue::new();
+    let (mut producer, consumer) = queue.split();
+    for (turn, at_ms) in events {
+        producer.enqueue(TurnEvent::new(*turn, *at_ms)).unwrap();
+    }
+    let mut reader = TurnReader::new(consumer);
+    steps_at_ms.iter().map(|now| reader.next_turn(*now)).collect()
+}
+
+#[test]
+fn empty_queue_goes_straight() {
+    assert_eq!(read(&[], &[100]), [Turn::None]);
+}
+
+#[test]
+fn two_presses_in_one_step_turn_on_two_steps() {
+    let turns = read(&[(Turn::Left, 100), (Turn::Left, 250)], &[500, 1000, 1500]);
+    assert_eq!(turns, [Turn::Left, Turn::Left, Turn::None]);
+}
+
+#[test]
+fn left_then_right_later_is_kept() {
+    let turns = read(&[(Turn::Left, 100), (Turn::Right, 100 + CHORD_MS + 1)], &[500, 1000]);
+    assert_eq!(turns, [Turn::Left, Turn::Right]);
+}
+
+#[test]
+fn both_buttons_together_are_dropped() {
+    let turns = read(&[(Turn::Left, 100), (Turn::Right, 100 + CHORD_MS), (Turn::Right, 300)], &[500, 1000]);
+    assert_eq!(turns, [Turn::Right, Turn::None]);
+}
+
+#[test]
+fn none_events_are_skipped() {
+    let turns = read(&[(Turn::None, 100), (Turn::Right, 200)], &[500]);
+    assert_eq!(turns, [Turn::Right]);
+}
+
+#[test]
+fn stale_presses_are_dropped() {
+    let turns = read(&[(Turn::Left, 0), (Turn::Right, 1000)], &[STALE_MS + 1]);
+    assert_eq!(turns, [Turn::Right]);
+}
+
+#[test]
+fn clock_wrap_is_not_stale() {
+    let turns = read(&[(Turn::Left, u32::MAX - 10)], &[CHORD_MS + 20]);
+    assert_eq!(turns, [Turn::Left]);
+}
+
+#[test]
+fn full_queue_refuses_new_presses() {
+    let mut queue: Queue<TurnEvent, 4> = Queue::new();
+    let (mut producer, consumer) = queue.split();
+    for at in 0..3 {
+        producer.enqueue(TurnEvent::new(Turn::Left, at * 100)).unwrap();
+    }
+    assert!(producer.enqueue(TurnEvent::new(Turn::Right, 400)).is_err());
+    let mut reader = TurnReader::new(consumer);
+    assert_eq!(reader.len(), 3);
+    reader.clear();
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn chords_come_out_as_one_press() {
+    let mut queue: Queue<TurnEvent, 8> = Queue::new();
+    let (mut producer, consumer) = queue.split();
+    for (turn, at) in [(Turn::Right, 100), (Turn::Left, 110), (Turn::Left, 400)] {
+        producer.enqueue(TurnEvent::new(turn, at)).unwrap();
+    }
+    let mut reader = TurnReader::new(consumer);
+    assert_eq!(reader.next_press(500), Some(Press::AB));
+    assert_eq!(reader.next_press(500), Some(Press::A));
+    assert_eq!(reader.next_press(500), None);
+}
+
+#[test]
+fn chord_split_over_two_polls_is_still_one_press() {
+    let mut queue: Queue<TurnEvent, 8> = Queue::new();
+    let (mut producer, consumer) = queue.split();
+    let mut reader = TurnReader::new(consumer);
+    producer.enqueue(TurnEvent::new(Turn::Left, 100)).unwrap();
+    assert_eq!(reader.next_press(110), None);
+    producer.enqueue(TurnEvent::new(Turn::Right, 100 + CHORD_MS - 10)).unwrap();
+    assert_eq!(reader.next_press(100 + CHORD_MS), Some(Press::AB));
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn lone_press_comes_out_once_the_chord_time_is_over() {
+    let mut queue: Queue<TurnEvent, 8> = Queue::new();
+    let (mut producer, consumer) = queue.split();
+    let mut reader = TurnReader::new(consumer);
+    producer.enqueue(TurnEvent::new(Turn::Right, 100)).unwrap();
+    assert_eq!(reader.next_press(100), None);
+    assert_eq!(reader.len(), 1);
+    assert_eq!(reader.next_press(100 + CHORD_MS), Some(Press::B));
+    assert_eq!(reader.next_press(100 + CHORD_MS), None);
+}
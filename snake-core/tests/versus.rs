@@ -0,0 +1,293 @@
+use std::collections::VecDeque;
+
+use snake_core::{
+    coords::Coords,
+    movement::Turn,
+    rng::Prng,
+    versus::{FRAME_LEN, MessageDecoder, Link, LinkError, Message, Player, StepFrame, Versus, VersusStatus},
+};
+
+// One direction of the serial line, loses or garbles bytes now and then
+struct Line {
+    bytes: VecDeque<u8>,
+    rng: Prng,
+    // Out of 100 frames
+    lose: u32,
+    garble: u32,
+    cut: bool,
+}
+
+impl Line {
+    fn new(seed: u32, lose: u32, garble: u32) -> Self {
+        Self { bytes: VecDeque::new(), rng: Prng::new(seed), lose, garble, cut: false }
+    }
+
+    fn send(&mut self, message: Message) {
+        let roll = self.rng.random_u32() % 100;
+        if self.cut || roll < self.lose {
+            return;
+        }
+        let mut frame = message.encode();
+        if roll < self.lose + self.garble {
+            let at = self.rng.random_u32() as usize % FRAME_LEN;
+            frame[at] ^= 1 << (self.rng.random_u32() % 8);
+        }
+        self.bytes.extend(frame);
+    }
+}
+
+struct Board {
+    link: Link,
+    decoder: MessageDecoder,
+    versus: Option<Versus>,
+    turns: Vec<Turn>,
+    error: Option<LinkError>,
+}
+
+impl Board {
+    fn new(nonce: u32, turns: &[Turn]) -> Self {
+        Self { link: Link::new(nonce), decoder: MessageDecoder::new(), versus: None, turns: turns.to_vec(), error: None }
+    }
+
+    // One poll of the board: send, read what came in, step when both turns are there
+    fn poll(&mut self, out: &mut Line, incoming: &mut Line) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Some(message) = self.link.outgoing() {
+            out.send(message);
+        }
+        let mut heard = false;
+        while let Some(byte) = incoming.bytes.pop_front() {
+            if let Some(message) = self.decoder.feed(byte) {
+                heard = true;
+                if let Err(e) = self.link.receive(message) {
+                    self.error = Some(e);
+                    return;
+                }
+            }
+        }
+        if !heard && let Err(e) = self.link.poll_timeout() {
+            self.error = Some(e);
+            return;
+        }
+
+        if self.versus.is_none()
+            && let Some((seed, _)) = self.link.start()
+        {
+            self.versus = Some(Versus::new(seed));
+        }
+        let Some(versus) = self.versus.as_mut() else {
+            return;
+        };
+        if versus.status() != VersusStatus::Ongoing {
+            return;
+        }
+        if !self.link.has_turn() {
+            let turn = self.turns.get(self.link.step() as usize).copied().unwrap_or(Turn::None);
+            self.link.send_turn(turn, versus);
+        }
+        match self.link.ready() {
+            Ok(Some(turns)) => {
+                versus.step(turns);
+                self.link.next_step();
+            },
+            Ok(None) => (),
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
+
+// Runs both boards until both games are over or something went wrong
+fn run(a: &mut Board, b: &mut Board, a_to_b: &mut Line, b_to_a: &mut Line, polls: usize) {
+    for _ in 0..polls {
+        a.poll(a_to_b, b_to_a);
+        b.poll(b_to_a, a_to_b);
+        let done = |board: &Board| board.versus.as_ref().is_some_and(|v| v.status() != VersusStatus::Ongoing);
+        let stopped = |board: &Board| done(board) || board.error.is_some();
+        if stopped(a) && stopped(b) {
+            break;
+        }
+    }
+}
+
+const A_TURNS: [Turn; 8] = [Turn::None, Turn::Right, Turn::None, Turn::Left, Turn::None, Turn::Right, Turn::Right, Turn::None];
+const B_TURNS: [Turn; 6] = [Turn::Left, Turn::None, Turn::None, Turn::Right, Turn::Left, Turn::None];
+
+fn assert_same_game(a: &Board, b: &Board) {
+    let (va, vb) = (a.versus.as_ref().unwrap(), b.versus.as_ref().unwrap());
+    assert_eq!(va.status(), vb.status());
+    assert_eq!(va.steps(), vb.steps());
+    assert_eq!(va.checksum(), vb.checksum());
+    assert_eq!(va.matrix(Player::First), vb.matrix(Player::First));
+}
+
+#[test]
+fn frames_survive_encoding() {
+    let messages = [
+        Message::Hello { nonce: 0xdead_beef },
+        Message::Step(StepFrame { step: 513, turn: Turn::Left, prev_turn: Turn::Right, food: Coords::new(4, 3), check: 0x5a }),
+    ];
+    let mut decoder = MessageDecoder::new();
+    let bytes: Vec<u8> = [0x00, 0xa5, 0x17].into_iter().chain(messages.iter().flat_map(|m| m.encode())).collect();
+    let decoded: Vec<Message> = bytes.iter().filter_map(|b| decoder.feed(*b)).collect();
+    assert_eq!(decoded, messages);
+}
+
+#[test]
+fn garbled_frame_is_dropped() {
+    let mut frame = Message::Hello { nonce: 7 }.encode();
+    frame[3] ^= 0x10;
+    let mut decoder = MessageDecoder::new();
+    assert!(frame.iter().all(|b| decoder.feed(*b).is_none()));
+    let good = Message::Hello { nonce: 7 };
+    assert_eq!(good.encode().iter().filter_map(|b| decoder.feed(*b)).next(), Some(good));
+}
+
+#[test]
+fn larger_nonce_plays_first_with_the_same_seed() {
+    let (mut a, mut b) = (Link::new(10), Link::new(3));
+    a.receive(Message::Hello { nonce: 3 }).unwrap();
+    b.receive(Message::Hello { nonce: 10 }).unwrap();
+    assert_eq!(a.start(), Some((10 ^ 3, Player::First)));
+    assert_eq!(b.start(), Some((10 ^ 3, Player::Second)));
+}
+
+#[test]
+fn snakes_crash_into_each_other() {
+    let mut versus: Versus = Versus::new(42);
+    // First at (1, 1) going right, second at (3, 3) going left: turn them towards each other
+    versus.step([Turn::Right, Turn::Right]);
+    assert_eq!(versus.game(Player::First).snake().head, Coords::new(2, 1));
+    assert_eq!(versus.game(Player::Second).snake().head, Coords::new(2, 3));
+    // Both heads end up on (2, 2)
+    versus.step([Turn::Left, Turn::Left]);
+    assert_eq!(versus.status(), VersusStatus::Draw);
+}
+
+#[test]
+fn running_into_the_other_snake_loses() {
+    let mut versus: Versus = Versus::new(42);
+    versus.step([Turn::Right, Turn::Right]);
+    // With seed 42 the food ends up on (4, 1): first goes down and eats it, so its tail stays on (2, 1)
+    // while the second one goes left into (2, 2) and then (2, 1)
+    versus.step([Turn::None, Turn::Left]);
+    assert_eq!(versus.status(), VersusStatus::Ongoing);
+    versus.step([Turn::None, Turn::None]);
+    assert_eq!(versus.status(), VersusStatus::Won(Player::First));
+}
+
+#[test]
+fn food_keeps_clear_of_both_snakes() {
+    let mut versus: Versus = Versus::new(9);
+    for _ in 0..40 {
+        if versus.status() != VersusStatus::Ongoing {
+            break;
+        }
+        let food = versus.food();
+        for player in [Player::First, Player::Second] {
+            assert!(!versus.game(player).snake().contains(&food));
+            assert_eq!(versus.game(player).food(), food);
+        }
+        versus.step([Turn::None, Turn::Left]);
+    }
+}
+
+#[test]
+fn snakes_filling_the_board_is_a_draw() {
+    // The food goes in the last free cell, after that there is nowhere left for it
+    let mut versus: Versus<3, 2> = Versus::new(0);
+    versus.step([Turn::None, Turn::None]);
+    versus.step([Turn::None, Turn::None]);
+    let cells = [Player::First, Player::Second].map(|p| versus.game(p).snake().tail_len() + 1);
+    assert_eq!(cells.iter().sum::<usize>(), 6);
+    assert_eq!(versus.status(), VersusStatus::Draw);
+}
+
+#[test]
+fn perfect_line_stays_in_step() {
+    let (mut a, mut b) = (Board::new(0x1234, &A_TURNS), Board::new(0x9876, &B_TURNS));
+    let (mut a_to_b, mut b_to_a) = (Line::new(1, 0, 0), Line::new(2, 0, 0));
+    run(&mut a, &mut b, &mut a_to_b, &mut b_to_a, 1000);
+    assert_eq!((a.error, b.error), (None, None));
+    assert_ne!(a.versus.as_ref().unwrap().status(), VersusStatus::Ongoing);
+    assert_same_game(&a, &b);
+}
+
+#[test]
+fn lossy_line_stays_in_step() {
+    for seed in 1..=20 {
+        let (mut a, mut b) = (Board::new(0x1234 + seed, &A_TURNS), Board::new(0x9876, &B_TURNS));
+        let (mut a_to_b, mut b_to_a) = (Line::new(seed, 30, 10), Line::new(seed * 7, 30, 10));
+        run(&mut a, &mut b, &mut a_to_b, &mut b_to_a, 5000);
+        assert_eq!((a.error, b.error), (None, None), "seed {seed}");
+        assert_ne!(a.versus.as_ref().unwrap().status(), VersusStatus::Ongoing, "seed {seed}");
+        assert_same_game(&a, &b);
+    }
+}
+
+#[test]
+fn cut_line_is_reported_lost() {
+    let (mut a, mut b) = (Board::new(1, &A_TURNS), Board::new(2, &B_TURNS));
+    let (mut a_to_b, mut b_to_a) = (Line::new(1, 0, 0), Line::new(2, 0, 0));
+    run(&mut a, &mut b, &mut a_to_b, &mut b_to_a, 4);
+    a_to_b.cut = true;
+    b_to_a.cut = true;
+    run(&mut a, &mut b, &mut a_to_b, &mut b_to_a, 1000);
+    assert!(matches!(a.error, Some(LinkError::Lost { .. })));
+    assert!(matches!(b.error, Some(LinkError::Lost { .. })));
+}
+
+// A frame as the other board sends it, always one step ahead of `link`
+fn ahead(link: &Link, food: Coords, check: u8) -> Message {
+    Message::Step(StepFrame { step: link.step() + 1, turn: Turn::None, prev_turn: Turn::None, food, check })
+}
+
+#[test]
+fn peer_one_step_ahead_is_still_checked() {
+    let mut link = Link::new(0x1234);
+    link.receive(Message::Hello { nonce: 0x9876 }).unwrap();
+    let mut versus: Versus = Versus::new(link.start().unwrap().0);
+    // The frame for step 0 got lost, the one for step 1 says what the other game looks like then
+    link.send_turn(Turn::None, &versus);
+    link.receive(ahead(&link, Coords::new(0, 0), versus.checksum() ^ 0xff)).unwrap();
+    let turns = link.ready().unwrap().unwrap();
+    versus.step(turns);
+    link.next_step();
+
+    // Lost again, only step 2 comes through
+    link.send_turn(Turn::None, &versus);
+    link.receive(ahead(&link, versus.food(), versus.checksum())).unwrap();
+    assert_eq!(link.ready(), Err(LinkError::Desync { step: 1 }));
+}
+
+#[test]
+fn peer_one_step_ahead_with_the_same_game_carries_on() {
+    let mut link = Link::new(0x1234);
+    link.receive(Message::Hello { nonce: 0x9876 }).unwrap();
+    let seed = link.start().unwrap().0;
+    let mut versus: Versus = Versus::new(seed);
+    // The other board's game, a step ahead with the same turns
+    let mut peer: Versus = Versus::new(seed);
+    peer.step([Turn::None; 2]);
+    for _ in 0..5 {
+        link.send_turn(Turn::None, &versus);
+        link.receive(ahead(&link, peer.food(), peer.checksum())).unwrap();
+        let turns = link.ready().unwrap().unwrap();
+        versus.step(turns);
+        peer.step(turns);
+        link.next_step();
+    }
+    assert_eq!(link.step(), 5);
+}
+
+#[test]
+fn different_game_is_reported_as_desync() {
+    let (mut a, mut b) = (Board::new(0x1234, &A_TURNS), Board::new(0x9876, &B_TURNS));
+    let (mut a_to_b, mut b_to_a) = (Line::new(1, 0, 0), Line::new(2, 0, 0));
+    run(&mut a, &mut b, &mut a_to_b, &mut b_to_a, 3);
+    // One board ends up with a different game, e.g. a bit flipped in RAM
+    b.versus = Some(Versus::new(0xbad));
+    run(&mut a, &mut b, &mut a_to_b, &mut b_to_a, 1000);
+    assert!(matches!(a.error, Some(LinkError::Desync { .. })) || matches!(b.error, Some(LinkError::Desync { .. })));
+}
@@ -0,0 +1,318 @@
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    sync::mpsc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use heapless::spsc::Queue;
+use led_matrix::mirror::{MirrorDecoder, MirrorFrame};
+use snake_core::{
+    Game,
+    level::{LEVELS, Level},
+    movement::{GameStatus, Turn},
+    replay::ReplayLog,
+    rules::GameRules,
+    snapshot,
+    speed::SpeedCurve,
+    turn_queue::{TurnEvent, TurnReader},
+};
+
+// Same settings as the firmware main loop
+const HEAD_BRIGHTNESS: u8 = 6;
+const TAIL_BRIGHTNESS: u8 = 3;
+const FOOD_BRIGHTNESS: u8 = 9;
+const WALL_BRIGHTNESS: u8 = 1;
+const REPLAY_LEN: usize = 1024;
+const TURN_QUEUE_LEN: usize = 8;
+
+enum Input {
+    Turn(Turn),
+    Quit,
+}
+
+// Usage: cargo run [--8x8] [seed in hex], e.g. the seed from a replay log dumped by the firmware
+//        cargo run -- --crash <snapshot in hex>, the crash dump the firmware prints after a panic
+//        cargo run -- --mirror <serial port or capture>, draws what the board's display shows, live.
+//            Set the port up first, e.g. `stty -F /dev/ttyACM0 115200 raw`
+//        cargo run -- --frames <capture>, the same frames as text, one after the other
+fn main() -> io::Result<()> {
+    let mut big_board = false;
+    let mut seed = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--crash" {
+            let hex: String = args.collect();
+            return show_crash(&hex);
+        } else if arg == "--mirror" || arg == "--frames" {
+            let path = args.next().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no port or capture given"))?;
+            let source = File::open(path)?;
+            return if arg == "--mirror" { view_mirror(source) } else { print_frames(source) };
+        } else if arg == "--8x8" {
+            big_board = true;
+        } else {
+            seed = u32::from_str_radix(arg.trim_start_matches("0x"), 16).ok();
+        }
+    }
+    let mut seed = seed.unwrap_or_else(time_seed);
+
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = if big_board {
+        run::<8, 8>(&mut stdout, &mut seed, &[])
+    } else {
+        run::<5, 5>(&mut stdout, &mut seed, &LEVELS)
+    };
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn run<const W: usize, const H: usize>(
+    stdout: &mut impl Write,
+    seed: &mut u32,
+    levels: &'static [Level<W, H>],
+) -> io::Result<()> {
+    let clock = Instant::now();
+    let now_ms = || clock.elapsed().as_millis() as u32;
+    loop {
+        let mut game: Game<W, H> = Game::with_levels(*seed, GameRules::LEVELS, levels);
+        let mut replay_log: ReplayLog<REPLAY_LEN> = ReplayLog::new(*seed);
+        let mut queue: Queue<TurnEvent, TURN_QUEUE_LEN> = Queue::new();
+        let (mut producer, consumer) = queue.split();
+        let mut turns = TurnReader::new(consumer);
+
+        loop {
+            let matrix = game.game_matrix(HEAD_BRIGHTNESS, TAIL_BRIGHTNESS, FOOD_BRIGHTNESS, WALL_BRIGHTNESS);
+            draw(stdout, &matrix, &game)?;
+
+            // Keys are queued like the buttons on the board, so a step takes one press in order
+            let deadline = Instant::now() + Duration::from_millis(game.step_len_ms() as u64);
+            while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+                match read_input(left)? {
+                    Some(Input::Turn(t)) => {
+                        let _ = producer.enqueue(TurnEvent::new(t, now_ms()));
+                    },
+                    Some(Input::Quit) => return Ok(()),
+                    None => (),
+                }
+            }
+            let turn = turns.next_turn(now_ms());
+
+            match game.status {
+                GameStatus::Ongoing => {
+                    let _ = replay_log.record(turn);
+                    game.step(turn);
+                },
+                _ => {
+                    for _ in 0..3 {
+                        draw(stdout, &[[0; W]; H], &game)?;
+                        std::thread::sleep(Duration::from_millis(200));
+                        draw(stdout, &matrix, &game)?;
+                        std::thread::sleep(Duration::from_millis(200));
+                    }
+                    let score = game.score_matrix().map(|row| row.map(|v| v * 9));
+                    draw(stdout, &score, &game)?;
+                    queue!(stdout, cursor::MoveTo(0, H as u16 + 4), Print(&replay_log))?;
+                    stdout.flush()?;
+                    std::thread::sleep(Duration::from_millis(2000));
+                    break;
+                }
+            }
+        }
+        *seed = time_seed();
+    }
+}
+
+// Prints the board from a snapshot, no terminal tricks so it can be piped into a file
+fn show_crash(hex: &str) -> io::Result<()> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    // The firmware prints it as "crash <hex>", copied with or without the word
+    let hex = hex.trim().trim_start_matches("crash");
+    let bytes = parse_hex(hex).ok_or_else(|| invalid(format!("not hex: {hex}")))?;
+    let text = match snapshot::board_size(&bytes) {
+        Some((5, 5)) => {
+            let game: Game = Game::from_snapshot(&bytes, GameRules::LEVELS, &LEVELS).map_err(|e| invalid(format!("{e:?}")))?;
+            render(&game)
+        },
+        Some((8, 8)) => {
+            let game: Game<8, 8> = Game::from_snapshot(&bytes, GameRules::LEVELS, &[]).map_err(|e| invalid(format!("{e:?}")))?;
+            render(&game)
+        },
+        Some((width, height)) => return Err(invalid(format!("no levels for a {width}x{height} board"))),
+        None => return Err(invalid("not a snapshot".to_string())),
+    };
+    print!("{text}");
+    Ok(())
+}
+
+// Reads on its own thread so a quiet port does not keep q from quitting
+fn view_mirror(mut source: impl Read + Send + 'static) -> io::Result<()> {
+    let (frames, received) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut decoder = MirrorDecoder::new();
+        let mut buf = [0; 64];
+        while let Ok(len @ 1..) = source.read(&mut buf) {
+            for frame in buf[..len].iter().filter_map(|byte| decoder.feed(*byte)) {
+                if frames.send(frame).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let mut result = draw_mirror(&mut stdout, None, 0);
+    let mut lost = 0;
+    let mut last: Option<MirrorFrame> = None;
+    while result.is_ok() {
+        match read_input(Duration::from_millis(20)) {
+            Ok(Some(Input::Quit)) => break,
+            Ok(_) => (),
+            Err(e) => result = Err(e),
+        }
+        while let Ok(frame) = received.try_recv() {
+            if let Some(last) = last {
+                lost += frame.sequence.wrapping_sub(last.sequence).wrapping_sub(1) as u32;
+            }
+            last = Some(frame);
+            result = result.and_then(|_| draw_mirror(&mut stdout, Some(&frame), lost));
+        }
+    }
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn draw_mirror(stdout: &mut impl Write, frame: Option<&MirrorFrame>, lost: u32) -> io::Result<()> {
+    queue!(stdout, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All))?;
+    let image = frame.map_or([[0; 5]; 5], |frame| frame.image);
+    for (r, row) in image.iter().enumerate() {
+        queue!(stdout, cursor::MoveTo(0, r as u16))?;
+        for value in row {
+            queue!(stdout, Print(shade(*value)))?;
+        }
+    }
+    let status = match frame {
+        Some(frame) => format!("frame {}  lost {}", frame.sequence, lost),
+        None => "waiting for the board".to_string(),
+    };
+    queue!(stdout, cursor::MoveTo(0, 6), Print(status), cursor::MoveTo(0, 7), Print("q to quit"))?;
+    stdout.flush()
+}
+
+// No terminal tricks, so a capture can be checked with diff
+fn print_frames(mut source: impl Read) -> io::Result<()> {
+    let mut bytes = Vec::new();
+    source.read_to_end(&mut bytes)?;
+    let mut decoder = MirrorDecoder::new();
+    for frame in bytes.iter().filter_map(|byte| decoder.feed(*byte)) {
+        println!("frame {}", frame.sequence);
+        for row in frame.image {
+            println!("{}", row.iter().map(|value| shade(*value)).collect::<String>());
+        }
+    }
+    Ok(())
+}
+
+fn render<const W: usize, const H: usize>(game: &Game<W, H>) -> String {
+    let matrix = game.game_matrix(HEAD_BRIGHTNESS, TAIL_BRIGHTNESS, FOOD_BRIGHTNESS, WALL_BRIGHTNESS);
+    let mut text = String::new();
+    for row in matrix {
+        text.extend(row.iter().map(|value| shade(*value)));
+        text.push('\n');
+    }
+    text.push_str(&format!(
+        "{:?}  score {}  speed {}  level {}  steps {}\nheading {:?}  tail {}  food {:?}\n",
+        game.status,
+        game.score,
+        game.speed(),
+        game.level() + 1,
+        game.steps(),
+        game.snake().direction,
+        game.snake().tail_len(),
+        game.food()
+    ));
+    text
+}
+
+// Spaces are allowed, e.g. when the dump was copied over several lines
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = hex.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+fn read_input(timeout: Duration) -> io::Result<Option<Input>> {
+    if !event::poll(timeout)? {
+        return Ok(None);
+    }
+    let input = match event::read()? {
+        Event::Key(KeyEvent { code, modifiers, kind: KeyEventKind::Press, .. }) => match code {
+            KeyCode::Left | KeyCode::Char('a') => Some(Input::Turn(Turn::Left)),
+            KeyCode::Right | KeyCode::Char('d') => Some(Input::Turn(Turn::Right)),
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Some(Input::Quit),
+            KeyCode::Char('q') | KeyCode::Esc => Some(Input::Quit),
+            _ => None,
+        },
+        _ => None,
+    };
+    Ok(input)
+}
+
+fn draw<const W: usize, const H: usize>(stdout: &mut impl Write, matrix: &[[u8; W]; H], game: &Game<W, H>) -> io::Result<()> {
+    queue!(stdout, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All))?;
+    for (r, row) in matrix.iter().enumerate() {
+        queue!(stdout, cursor::MoveTo(0, r as u16))?;
+        for value in row {
+            queue!(stdout, Print(shade(*value)))?;
+        }
+    }
+    queue!(
+        stdout,
+        cursor::MoveTo(0, H as u16 + 1),
+        Print(format!(
+            "{:?}  score {}  speed {}  level {}",
+            game.status,
+            game.score,
+            SpeedCurve::CLASSIC.level(game),
+            game.level() + 1
+        )),
+        cursor::MoveTo(0, H as u16 + 2),
+        Print("left/a, right/d to turn, q to quit"),
+    )?;
+    stdout.flush()
+}
+
+// Two characters per LED so the grid comes out roughly square, brightness 0..=9 like GreyscaleImage
+fn shade(brightness: u8) -> &'static str {
+    match brightness {
+        0 => "··",
+        1..=3 => "░░",
+        4..=6 => "▒▒",
+        7..=8 => "▓▓",
+        _ => "██",
+    }
+}
+
+fn time_seed() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() ^ d.as_secs() as u32)
+        .unwrap_or(1)
+}